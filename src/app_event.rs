@@ -0,0 +1,130 @@
+use std::future::Future;
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use ulid::Ulid;
+
+use crate::app_state::UserState;
+use crate::connection::ConnectionState;
+use crate::rpc::channel::ChannelId;
+use crate::rpc::connect::event::ServerEvent;
+use crate::rpc::error::ClientError;
+use crate::rpc::message::MessageId;
+use crate::rpc::server::ServerId;
+use crate::rpc::ycchat::v1::models::{Message, Reaction, Server, ServerMember, User};
+use crate::rpc::ycchat::v1::services::channel::SpeechResponse;
+use crate::rpc::ycchat::v1::services::message::ListMessagesResponse;
+use crate::ui::chat::ChannelTree;
+use crate::ui::Scene;
+
+/// Everything that reaches the UI from outside the terminal.
+pub enum AppEvent {
+    /// result of a background task spawned with `EventSender::spawn`
+    Task(TaskResult),
+    /// pushed by the server over the Connect stream
+    Server(ServerEvent),
+    Connection(ConnectionState),
+}
+
+impl AppEvent {
+    /// The scene that handles this event.
+    pub fn scene(&self) -> Scene {
+        match self {
+            AppEvent::Task(TaskResult::RestoreSession(_)) => Scene::Main,
+            AppEvent::Task(TaskResult::SignIn(_)) => Scene::SignIn,
+            AppEvent::Task(TaskResult::SignUp(_)) => Scene::SignUp,
+            AppEvent::Task(TaskResult::Profile(_)) => Scene::Profile,
+            AppEvent::Task(TaskResult::PasswordUpdated(_)) => Scene::Account,
+            AppEvent::Task(TaskResult::AccountDeleted(_)) => Scene::DeleteAccount,
+            AppEvent::Task(_) | AppEvent::Server(_) | AppEvent::Connection(_) => Scene::Chat,
+        }
+    }
+}
+
+pub enum TaskResult {
+    /// the saved session, checked against the server
+    RestoreSession(Result<UserState, ClientError>),
+    SignIn(Result<UserState, ClientError>),
+    /// `None` if the account was created but the automatic sign-in failed
+    SignUp(Result<Option<UserState>, ClientError>),
+    Profile(Result<User, ClientError>),
+    PasswordUpdated(Result<(), ClientError>),
+    AccountDeleted(Result<(), ClientError>),
+    /// every page of servers
+    Servers(Result<Vec<Server>, ClientError>),
+    /// every page of the server's categories and channels
+    Channels(ServerId, Result<ChannelTree, ClientError>),
+    Members(ServerId, Result<Vec<ServerMember>, ClientError>),
+    Messages(ChannelId, Result<ListMessagesResponse, ClientError>),
+    /// messages missed while the Connect stream was down
+    Backfill(ChannelId, Result<Vec<Message>, ClientError>),
+    /// `original` is restored if the optimistic edit fails
+    MessageUpdated {
+        channel_id: ChannelId,
+        original: Message,
+        result: Result<Message, ClientError>,
+    },
+    /// `original` is restored if the optimistic deletion fails
+    MessageDeleted {
+        channel_id: ChannelId,
+        original: Message,
+        result: Result<(), ClientError>,
+    },
+    /// reactions of the messages that loaded, and the first error if some did not
+    Reactions {
+        channel_id: ChannelId,
+        reactions: Vec<(MessageId, Vec<Reaction>)>,
+        error: Option<ClientError>,
+    },
+    ReactionAdded {
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: String,
+        result: Result<Reaction, ClientError>,
+    },
+    /// `original` is restored if the optimistic removal fails
+    ReactionDeleted {
+        channel_id: ChannelId,
+        message_id: MessageId,
+        original: Reaction,
+        result: Result<(), ClientError>,
+    },
+    /// the acknowledgement delay for `MessageId` ran out
+    AcknowledgeDue(ChannelId, MessageId),
+    Acknowledged {
+        channel_id: ChannelId,
+        message_id: MessageId,
+        result: Result<(), ClientError>,
+    },
+    /// `local_id` identifies the outgoing message the result belongs to
+    Speech {
+        channel_id: ChannelId,
+        local_id: Ulid,
+        result: Result<SpeechResponse, ClientError>,
+    },
+}
+
+pub fn channel() -> (EventSender, UnboundedReceiver<AppEvent>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    (EventSender(sender), receiver)
+}
+
+#[derive(Clone)]
+pub struct EventSender(UnboundedSender<AppEvent>);
+
+impl EventSender {
+    pub fn send(&self, event: AppEvent) {
+        // the receiver only goes away when the app is shutting down
+        let _ = self.0.send(event);
+    }
+
+    /// Runs `task` off the UI loop and delivers its result as `AppEvent::Task`.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = TaskResult> + Send + 'static,
+    {
+        let sender = self.clone();
+
+        tokio::spawn(async move { sender.send(AppEvent::Task(task.await)) });
+    }
+}
@@ -1,12 +1,71 @@
+use crate::app_event::EventSender;
+use crate::config::Config;
+use crate::connection::Connection;
+use crate::rpc::client::RpcClient;
 use crate::rpc::ycchat::v1::{models::User, services::auth::SignInResponse};
+use crate::session::{Session, SessionStore};
 
 pub struct AppState {
+    pub config: Config,
+    pub rpc: RpcClient,
+    pub events: EventSender,
+    pub connection: Connection,
     pub user: Option<UserState>,
+    /// `None` when there is nowhere to keep the session between launches
+    pub session: Option<SessionStore>,
+    /// shown once by the next scene that takes it, e.g. why the user was signed out
+    pub notice: Option<String>,
 }
 
 impl AppState {
-    pub fn new() -> Self {
-        Self { user: None }
+    pub fn new(config: Config, events: EventSender, session: Option<SessionStore>) -> Self {
+        let rpc = RpcClient::new(config.channel_endpoint(), config.timeouts.clone());
+
+        Self {
+            config,
+            rpc,
+            events,
+            connection: Connection::default(),
+            user: None,
+            session,
+            notice: None,
+        }
+    }
+
+    /// Remembers the signed-in user for the next launch; failing to only costs a sign-in.
+    pub fn save_session(&self) {
+        let (store, user) = match (&self.session, &self.user) {
+            (Some(store), Some(user)) => (store, user),
+            _ => return,
+        };
+
+        let session = Session {
+            username: user.username.clone(),
+            sign_in_response: user.sign_in_response.clone(),
+        };
+        if let Err(err) = store.save(&session) {
+            tracing::warn!(error = %err, "failed to save the session");
+        }
+    }
+
+    /// Forgets the user in memory, on disk and in the RPC client; `notice` is shown by the
+    /// next scene.
+    pub fn sign_out(&mut self, notice: Option<String>) {
+        self.user = None;
+        self.connection.stop();
+        self.clear_session();
+        self.notice = notice;
+
+        let rpc = self.rpc.clone();
+        tokio::spawn(async move { rpc.clear_auth().await });
+    }
+
+    pub fn clear_session(&self) {
+        if let Some(store) = &self.session {
+            if let Err(err) = store.clear() {
+                tracing::warn!(error = %err, "failed to remove the saved session");
+            }
+        }
     }
 }
 
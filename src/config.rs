@@ -0,0 +1,363 @@
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::PathBuf, time::Duration};
+
+use clap::Parser;
+use http::Uri;
+use serde::Deserialize;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tracing_subscriber::filter::LevelFilter;
+
+use crate::rpc::deadline::Timeouts;
+
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:50051";
+const ENDPOINT_ENV: &str = "YCCHAT_ENDPOINT";
+const CA_CERT_ENV: &str = "YCCHAT_CA_CERT";
+const CLIENT_CERT_ENV: &str = "YCCHAT_CLIENT_CERT";
+const CLIENT_KEY_ENV: &str = "YCCHAT_CLIENT_KEY";
+const TLS_DOMAIN_ENV: &str = "YCCHAT_TLS_DOMAIN";
+const PROFILE_ENV: &str = "YCCHAT_PROFILE";
+const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Parser)]
+#[command(name = "ycchat", version, about = "ycchat terminal client")]
+pub struct Args {
+    /// server endpoint (e.g. http://127.0.0.1:50051 or https://chat.example.com)
+    #[arg(long)]
+    pub endpoint: Option<String>,
+
+    /// PEM CA bundle trusted in addition to the system roots (https only)
+    #[arg(long)]
+    pub ca_cert: Option<PathBuf>,
+
+    /// PEM client certificate for mutual TLS (requires --client-key)
+    #[arg(long)]
+    pub client_cert: Option<PathBuf>,
+
+    /// PEM private key for --client-cert
+    #[arg(long)]
+    pub client_key: Option<PathBuf>,
+
+    /// domain name to verify the server certificate against
+    #[arg(long)]
+    pub tls_domain: Option<String>,
+
+    /// default deadline for each request, in seconds
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<f64>,
+
+    /// timeout for establishing the connection, in seconds
+    #[arg(long, value_name = "SECS")]
+    pub connect_timeout: Option<f64>,
+
+    /// log level: off, error, warn, info, debug or trace (default: info)
+    #[arg(long, value_name = "LEVEL")]
+    pub log_level: Option<String>,
+
+    /// directory for the daily rotated log files (default: <data dir>/ycchat/logs)
+    #[arg(long, value_name = "DIR")]
+    pub log_dir: Option<PathBuf>,
+
+    /// profile name; every profile keeps its own saved session (default: default)
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// encrypt the saved session with a passphrase asked for on startup
+    #[arg(long)]
+    pub encrypt_session: bool,
+
+    /// config file path (default: <config dir>/ycchat/config.toml)
+    #[arg(long, short)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Default)]
+struct ConfigFile {
+    endpoint: Option<String>,
+    #[serde(default)]
+    tls: TlsFile,
+    #[serde(default)]
+    timeouts: TimeoutsFile,
+    #[serde(default)]
+    log: LogFile,
+    profile: Option<String>,
+    #[serde(default)]
+    session: SessionFile,
+}
+
+#[derive(Deserialize, Default)]
+struct TlsFile {
+    ca_cert: Option<PathBuf>,
+    client_cert: Option<PathBuf>,
+    client_key: Option<PathBuf>,
+    domain: Option<String>,
+}
+
+/// All values in seconds; `methods` maps a method name (e.g. `ListServers`) to its deadline.
+#[derive(Deserialize, Default)]
+struct TimeoutsFile {
+    default: Option<f64>,
+    connect: Option<f64>,
+    #[serde(default)]
+    methods: HashMap<String, f64>,
+}
+
+#[derive(Deserialize, Default)]
+struct LogFile {
+    level: Option<String>,
+    dir: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Default)]
+struct SessionFile {
+    #[serde(default)]
+    encrypt: bool,
+    dir: Option<PathBuf>,
+}
+
+#[derive(Clone)]
+pub enum EndpointSource {
+    Flag,
+    Env,
+    File(PathBuf),
+    Default,
+}
+
+impl fmt::Display for EndpointSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointSource::Flag => write!(f, "--endpoint"),
+            EndpointSource::Env => write!(f, "${}", ENDPOINT_ENV),
+            EndpointSource::File(path) => write!(f, "{}", path.display()),
+            EndpointSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Config {
+    pub endpoint: Uri,
+    pub endpoint_source: EndpointSource,
+    pub is_tls: bool,
+    pub timeouts: Timeouts,
+    pub log_level: LevelFilter,
+    /// `None` when no data directory is known and `--log-dir` was not given
+    pub log_dir: Option<PathBuf>,
+    pub profile: String,
+    /// `None` when no data directory is known and `session.dir` was not set
+    pub session_path: Option<PathBuf>,
+    pub encrypt_session: bool,
+    channel_endpoint: Endpoint,
+}
+
+impl Config {
+    /// Resolves every setting with the precedence
+    /// command-line flag > `$YCCHAT_*` environment variable > config file > default.
+    pub fn load(args: Args) -> Result<Self, Box<dyn Error>> {
+        let (file_path, is_explicit_path) = match args.config {
+            Some(path) => (Some(path), true),
+            None => (default_config_path(), false),
+        };
+
+        let file = match &file_path {
+            Some(path) => read_config_file(path, is_explicit_path)?,
+            None => ConfigFile::default(),
+        };
+
+        let (endpoint, endpoint_source) = if let Some(endpoint) = args.endpoint {
+            (endpoint, EndpointSource::Flag)
+        } else if let Ok(endpoint) = std::env::var(ENDPOINT_ENV) {
+            (endpoint, EndpointSource::Env)
+        } else if let (Some(endpoint), Some(path)) = (file.endpoint, file_path) {
+            (endpoint, EndpointSource::File(path))
+        } else {
+            (DEFAULT_ENDPOINT.to_string(), EndpointSource::Default)
+        };
+
+        let endpoint = parse_endpoint(&endpoint)
+            .map_err(|err| format!("invalid endpoint from {}: {}", endpoint_source, err))?;
+
+        let tls = TlsOptions {
+            ca_cert: args.ca_cert.or(env_path(CA_CERT_ENV)).or(file.tls.ca_cert),
+            client_cert: args
+                .client_cert
+                .or(env_path(CLIENT_CERT_ENV))
+                .or(file.tls.client_cert),
+            client_key: args
+                .client_key
+                .or(env_path(CLIENT_KEY_ENV))
+                .or(file.tls.client_key),
+            domain: args
+                .tls_domain
+                .or(std::env::var(TLS_DOMAIN_ENV).ok())
+                .or(file.tls.domain),
+        };
+
+        let mut timeouts = Timeouts::default();
+        if let Some(secs) = args.timeout.or(file.timeouts.default) {
+            timeouts.default = parse_secs("timeout", secs)?;
+        }
+        if let Some(secs) = args.connect_timeout.or(file.timeouts.connect) {
+            timeouts.connect = parse_secs("connect timeout", secs)?;
+        }
+        for (method, secs) in file.timeouts.methods {
+            let timeout = parse_secs(&method, secs)?;
+            timeouts.methods.insert(method, timeout);
+        }
+
+        let log_level = match args.log_level.or(file.log.level) {
+            Some(level) => level
+                .parse()
+                .map_err(|_| format!("invalid log level: {}", level))?,
+            None => LevelFilter::INFO,
+        };
+        let log_dir = args.log_dir.or(file.log.dir).or_else(default_log_dir);
+
+        let profile = args
+            .profile
+            .or(std::env::var(PROFILE_ENV).ok())
+            .or(file.profile)
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        if profile.is_empty()
+            || !profile
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("invalid profile name: {}", profile).into());
+        }
+        let session_path = file
+            .session
+            .dir
+            .or_else(default_session_dir)
+            .map(|dir| dir.join(format!("{}.json", profile)));
+        let encrypt_session = args.encrypt_session || file.session.encrypt;
+
+        let is_tls = endpoint.scheme_str() == Some("https");
+
+        let mut channel_endpoint =
+            Endpoint::from(endpoint.clone()).connect_timeout(timeouts.connect);
+        if is_tls {
+            channel_endpoint = channel_endpoint.tls_config(tls.client_tls_config()?)?;
+        } else if !tls.is_empty() {
+            return Err("TLS options require an https:// endpoint".into());
+        }
+
+        Ok(Self {
+            endpoint,
+            endpoint_source,
+            is_tls,
+            timeouts,
+            log_level,
+            log_dir,
+            profile,
+            session_path,
+            encrypt_session,
+            channel_endpoint,
+        })
+    }
+
+    pub fn channel_endpoint(&self) -> Endpoint {
+        self.channel_endpoint.clone()
+    }
+}
+
+struct TlsOptions {
+    ca_cert: Option<PathBuf>,
+    client_cert: Option<PathBuf>,
+    client_key: Option<PathBuf>,
+    domain: Option<String>,
+}
+
+impl TlsOptions {
+    fn is_empty(&self) -> bool {
+        self.ca_cert.is_none()
+            && self.client_cert.is_none()
+            && self.client_key.is_none()
+            && self.domain.is_none()
+    }
+
+    /// System roots are always trusted (tonic `tls-roots`); `ca_cert` is added on top.
+    fn client_tls_config(&self) -> Result<ClientTlsConfig, Box<dyn Error>> {
+        let mut tls_config = ClientTlsConfig::new();
+
+        if let Some(path) = &self.ca_cert {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(read_pem(path)?));
+        }
+
+        match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => {
+                tls_config =
+                    tls_config.identity(Identity::from_pem(read_pem(cert)?, read_pem(key)?));
+            }
+            (None, None) => {}
+            _ => return Err("--client-cert and --client-key must be given together".into()),
+        }
+
+        if let Some(domain) = &self.domain {
+            tls_config = tls_config.domain_name(domain.clone());
+        }
+
+        Ok(tls_config)
+    }
+}
+
+fn env_path(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key).map(PathBuf::from)
+}
+
+fn read_pem(path: &PathBuf) -> Result<Vec<u8>, Box<dyn Error>> {
+    fs::read(path).map_err(|err| format!("{}: {}", path.display(), err).into())
+}
+
+fn parse_secs(name: &str, secs: f64) -> Result<Duration, Box<dyn Error>> {
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| {
+            format!(
+                "invalid {}: {} (expected a positive number of seconds)",
+                name, secs
+            )
+            .into()
+        })
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ycchat").join("config.toml"))
+}
+
+fn default_log_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("ycchat").join("logs"))
+}
+
+fn default_session_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("ycchat").join("sessions"))
+}
+
+fn read_config_file(path: &PathBuf, is_explicit_path: bool) -> Result<ConfigFile, Box<dyn Error>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound && !is_explicit_path => {
+            return Ok(ConfigFile::default());
+        }
+        Err(err) => return Err(format!("{}: {}", path.display(), err).into()),
+    };
+
+    let file = toml::from_str(&content).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    Ok(file)
+}
+
+fn parse_endpoint(endpoint: &str) -> Result<Uri, Box<dyn Error>> {
+    let uri: Uri = endpoint.trim().parse()?;
+
+    match uri.scheme_str() {
+        Some("http") | Some("https") => {}
+        Some(scheme) => return Err(format!("unsupported scheme \"{}\"", scheme).into()),
+        None => return Err("missing scheme (expected http:// or https://)".into()),
+    }
+
+    if uri.host().is_none() {
+        return Err("missing host".into());
+    }
+
+    Ok(uri)
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::app_event::{AppEvent, EventSender};
+use crate::rpc::client::RpcClient;
+use crate::rpc::connect::event::ServerEvent;
+use crate::rpc::retry;
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// waiting before the `attempt`-th reconnect
+    Reconnecting {
+        attempt: u32,
+    },
+}
+
+/// Background task that keeps the Connect stream open for the signed-in user,
+/// reconnecting with backoff, and forwards its events as `AppEvent::Server`.
+#[derive(Default)]
+pub struct Connection {
+    task: Option<JoinHandle<()>>,
+}
+
+impl Connection {
+    pub fn start(&mut self, rpc: RpcClient, events: EventSender) {
+        self.stop();
+
+        self.task = Some(tokio::spawn(supervise(rpc, events)));
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+async fn supervise(rpc: RpcClient, events: EventSender) {
+    let mut attempt = 0;
+
+    events.send(AppEvent::Connection(ConnectionState::Connecting));
+
+    loop {
+        if run(&rpc, &events).await {
+            attempt = 0;
+        }
+
+        attempt += 1;
+        events.send(AppEvent::Connection(ConnectionState::Reconnecting {
+            attempt,
+        }));
+
+        let delay = retry::backoff(RECONNECT_BASE_DELAY, RECONNECT_MAX_DELAY, attempt - 1);
+        tracing::info!(attempt, delay_ms = delay.as_millis() as u64, "reconnecting");
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Streams until the connection ends; returns whether the stream was opened at all.
+async fn run(rpc: &RpcClient, events: &EventSender) -> bool {
+    let mut stream = match rpc.connect().connect().await {
+        Ok(stream) => stream,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to open the connect stream");
+            return false;
+        }
+    };
+
+    events.send(AppEvent::Connection(ConnectionState::Connected));
+
+    loop {
+        match stream.message().await {
+            Ok(Some(response)) => {
+                if let Some(event) = ServerEvent::decode(response) {
+                    events.send(AppEvent::Server(event));
+                }
+            }
+            Ok(None) => {
+                tracing::info!("connect stream closed by the server");
+                return true;
+            }
+            Err(status) => {
+                tracing::warn!(code = ?status.code(), "connect stream failed");
+                return true;
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::{error::Error, fs};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+
+use crate::config::Config;
+
+const LOG_FILE_PREFIX: &str = "ycchat.log";
+
+/// Sends `tracing` events to a daily rotated file in `config.log_dir`.
+///
+/// The terminal is owned by the TUI, so nothing is ever written to stdout/stderr.
+/// The returned guard flushes pending lines on drop and must outlive the app.
+pub fn init(config: &Config) -> Result<Option<WorkerGuard>, Box<dyn Error>> {
+    let dir = match &config.log_dir {
+        Some(dir) if config.log_level != LevelFilter::OFF => dir,
+        _ => return Ok(None),
+    };
+
+    fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+
+    let (writer, guard) =
+        tracing_appender::non_blocking(tracing_appender::rolling::daily(dir, LOG_FILE_PREFIX));
+
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_max_level(config.log_level)
+        .try_init()
+        .map_err(|err| err.to_string())?;
+
+    Ok(Some(guard))
+}
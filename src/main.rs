@@ -1,25 +1,63 @@
 use app_state::AppState;
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture};
+use clap::Parser;
+use config::{Args, Config};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture, EventStream};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, LeaveAlternateScreen};
 use crossterm::{execute, terminal::EnterAlternateScreen};
+use futures::StreamExt;
 use ratatui::prelude::{Backend, CrosstermBackend};
 use ratatui::Terminal;
+use session::SessionStore;
 use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::{error::Error, io};
+use tokio::time::MissedTickBehavior;
+use ui::account::AccountUi;
 use ui::after_sign_in::AfterSignInUi;
+use ui::chat::ChatUi;
+use ui::delete_account::DeleteAccountUi;
 use ui::profile::ProfileUi;
 use ui::sign_in::SignInUi;
 use ui::sign_up::SignUpUi;
 use ui::welcome::WelcomeUi;
 use ui::{Scene, Ui};
 
+mod app_event;
 mod app_state;
+mod config;
+mod connection;
+mod logging;
 mod rpc;
+mod session;
 mod ui;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let config = match Config::load(Args::parse()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("ycchat: {}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let _log_guard = match logging::init(&config) {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("ycchat: failed to set up logging: {}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let session = match open_session_store(&config) {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("ycchat: {}", err);
+            std::process::exit(2);
+        }
+    };
+
     enable_raw_mode()?;
 
     let mut stderr = io::stderr();
@@ -28,7 +66,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stderr);
     let mut terminal = Terminal::new(backend)?;
 
-    let _ = run_app(&mut terminal).await;
+    let _ = run_app(&mut terminal, config, session).await;
 
     disable_raw_mode()?;
     execute!(
@@ -41,47 +79,174 @@ async fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<bool> {
-    let app_state = AppState::new();
+const TICK_RATE: Duration = Duration::from_millis(250);
+const SESSION_PASSPHRASE_ENV: &str = "YCCHAT_SESSION_PASSPHRASE";
+
+/// Asks for the session passphrase before the terminal switches to raw mode.
+fn open_session_store(config: &Config) -> Result<Option<SessionStore>, Box<dyn Error>> {
+    let store = match SessionStore::new(config, None) {
+        Some(store) => store,
+        None => return Ok(None),
+    };
+    if !config.encrypt_session {
+        return Ok(Some(store));
+    }
+
+    let passphrase = match std::env::var(SESSION_PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => {
+            let prompt = inquire::Password::new("Session passphrase:")
+                .with_display_mode(inquire::PasswordDisplayMode::Masked);
+            // a new passphrase is typed twice, an existing one once
+            let prompt = if store.exists() {
+                prompt.without_confirmation()
+            } else {
+                prompt
+            };
+
+            prompt.prompt()?
+        }
+    };
+
+    Ok(SessionStore::new(config, Some(passphrase)))
+}
+
+struct Uis<'a> {
+    welcome: WelcomeUi<'a>,
+    sign_in: SignInUi<'a>,
+    sign_up: SignUpUi<'a>,
+    after_sign_in: AfterSignInUi<'a>,
+    profile: ProfileUi<'a>,
+    account: AccountUi<'a>,
+    delete_account: DeleteAccountUi<'a>,
+    chat: ChatUi<'a>,
+}
+
+impl<'a> Uis<'a> {
+    fn get(&mut self, scene: Scene) -> Option<&mut dyn Ui> {
+        match scene {
+            Scene::Main => Some(&mut self.welcome),
+            Scene::SignIn => Some(&mut self.sign_in),
+            Scene::SignUp => Some(&mut self.sign_up),
+            Scene::AfterSignIn => Some(&mut self.after_sign_in),
+            Scene::Profile => Some(&mut self.profile),
+            Scene::Account => Some(&mut self.account),
+            Scene::DeleteAccount => Some(&mut self.delete_account),
+            Scene::Chat => Some(&mut self.chat),
+            Scene::Quit => None,
+        }
+    }
+}
+
+/// Waits on terminal input, background task results / server events and a tick timer,
+/// and redraws only after something was handled.
+async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    config: Config,
+    session: Option<SessionStore>,
+) -> io::Result<bool> {
+    let (events, mut app_events) = app_event::channel();
+
+    let app_state = AppState::new(config, events, session);
+    let rpc = app_state.rpc.clone();
     let app_state = RefCell::new(app_state);
     let app_state = Arc::new(Mutex::new(app_state));
 
-    let mut welcome_ui = WelcomeUi::new();
-    let mut sign_in_ui = SignInUi::new(app_state.clone());
-    let mut sign_up_ui = SignUpUi::new(app_state.clone());
-    let mut after_sign_in_ui = AfterSignInUi::new(app_state.clone());
-    let mut profile_ui = ProfileUi::new(app_state);
+    let mut uis = Uis {
+        welcome: WelcomeUi::new(app_state.clone()),
+        sign_in: SignInUi::new(app_state.clone()),
+        sign_up: SignUpUi::new(app_state.clone()),
+        after_sign_in: AfterSignInUi::new(app_state.clone()),
+        profile: ProfileUi::new(app_state.clone()),
+        account: AccountUi::new(app_state.clone()),
+        delete_account: DeleteAccountUi::new(app_state.clone()),
+        chat: ChatUi::new(app_state.clone()),
+    };
+    uis.welcome.restore_session();
 
-    let mut current_secene = Scene::Main;
+    let mut terminal_events = EventStream::new();
+    let mut tick = tokio::time::interval(TICK_RATE);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let mut current_scene = Scene::Main;
+    let mut needs_redraw = true;
 
     loop {
-        let ui: &mut dyn Ui = match current_secene {
-            Scene::Main => &mut welcome_ui,
-            Scene::SignIn => &mut sign_in_ui,
-            Scene::SignUp => &mut sign_up_ui,
-            Scene::AfterSignIn => &mut after_sign_in_ui,
-            Scene::Profile => &mut profile_ui,
-            Scene::Quit => {
-                return Ok(true);
+        if needs_redraw {
+            match uis.get(current_scene) {
+                Some(ui) => terminal.draw(|f| ui.ui(f))?,
+                None => return Ok(true),
+            };
+            needs_redraw = false;
+        }
+
+        let next_scene = tokio::select! {
+            event = terminal_events.next() => {
+                let event = match event {
+                    Some(event) => event,
+                    None => return Ok(true),
+                };
+
+                needs_redraw = true;
+                match uis.get(current_scene) {
+                    Some(ui) => ui.event_handle(event).await.ok(),
+                    None => None,
+                }
+            }
+            Some(event) = app_events.recv() => {
+                needs_redraw = true;
+
+                // events go to the scene that owns them, even when it is not shown;
+                // only the shown scene may switch scenes
+                let owner = event.scene();
+                let next_scene = match uis.get(owner) {
+                    Some(ui) => ui.app_event_handle(event),
+                    None => None,
+                };
+
+                next_scene.filter(|_| owner == current_scene)
             }
+            _ = tick.tick() => None,
         };
 
-        terminal.draw(|f| ui.ui(f))?;
+        let mut next_scene = next_scene.filter(|scene| *scene != current_scene);
 
-        if event::poll(std::time::Duration::from_millis(50))? {
-            let res = ui.event_handle(event::read()).await;
+        // keep the saved session in step with the token the middleware renewed
+        if let Some(sign_in_response) = rpc.take_refreshed_auth().await {
+            let mut app_state = app_state.lock().unwrap();
+            let app_state = app_state.get_mut();
+            if let Some(user) = &mut app_state.user {
+                user.sign_in_response = sign_in_response;
+            }
+            app_state.save_session();
+        }
 
-            if let Ok(scene) = res {
-                current_secene = scene;
+        if rpc.take_session_expired()
+            && matches!(
+                next_scene.unwrap_or(current_scene),
+                Scene::AfterSignIn
+                    | Scene::Profile
+                    | Scene::Account
+                    | Scene::DeleteAccount
+                    | Scene::Chat
+            )
+        {
+            app_state.lock().unwrap().get_mut().sign_out(None);
+            uis.sign_in
+                .set_error_message(Some("Session expired. Please sign in again.".to_string()));
+            next_scene = Some(Scene::SignIn);
+        }
 
-                match current_secene {
-                    Scene::Quit => {
-                        return Ok(true);
-                    }
-                    _ => {
-                        continue;
-                    }
-                }
+        if let Some(scene) = next_scene {
+            if let Some(ui) = uis.get(current_scene) {
+                ui.on_leave();
+            }
+
+            current_scene = scene;
+            needs_redraw = true;
+
+            if let Some(ui) = uis.get(current_scene) {
+                ui.on_enter();
             }
         }
     }
@@ -1,25 +1,25 @@
-use tonic::transport::Channel;
-
+use super::client::AuthChannel;
+use super::error::ClientError;
 use super::ycchat::v1::services::auth::auth_service_client::AuthServiceClient;
 use super::ycchat::v1::services::auth::{
     SignInRequest, SignInResponse, SignUpRequest, SignUpResponse,
 };
 
 pub struct AuthService {
-    client: AuthServiceClient<Channel>,
+    client: AuthServiceClient<AuthChannel>,
 }
 
 impl AuthService {
-    pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let client = AuthServiceClient::connect("http://127.0.0.1:50051").await?;
-        Ok(Self { client })
+    pub fn new(channel: AuthChannel) -> Self {
+        let client = AuthServiceClient::new(channel);
+        Self { client }
     }
 
     pub async fn sign_in(
         &mut self,
         username: String,
         password: String,
-    ) -> Result<SignInResponse, Box<dyn std::error::Error>> {
+    ) -> Result<SignInResponse, ClientError> {
         let request = SignInRequest { username, password };
 
         let response = self.client.sign_in(request).await?;
@@ -32,7 +32,7 @@ impl AuthService {
         email: String,
         username: String,
         password: String,
-    ) -> Result<SignUpResponse, Box<dyn std::error::Error>> {
+    ) -> Result<SignUpResponse, ClientError> {
         let request = SignUpRequest {
             email,
             username,
@@ -1,39 +1,26 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use super::interceptor::AuthMiddleware;
+use super::client::RpcChannel;
+use super::error::ClientError;
+use super::name::{CategoryName, ServerName};
 use super::server::ServerId;
 use super::ycchat::v1::models::Category;
-use super::ycchat::v1::services::auth::SignInResponse;
 use super::ycchat::v1::services::server::category::category_service_client::CategoryServiceClient;
 use super::ycchat::v1::services::server::category::{
     CreateCategoryRequest, DeleteCategoryRequest, GetCategoryRequest, GetCategoryResponse,
     ListCategoriesRequest, ListCategoriesResponse, UpdateCategoryRequest,
 };
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
 use ulid::Ulid;
 
 pub type CategoryId = Ulid;
 
 pub struct CategoryService {
-    client: CategoryServiceClient<AuthMiddleware>,
+    client: CategoryServiceClient<RpcChannel>,
 }
 
 impl CategoryService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = CategoryServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
     pub async fn list_categories(
@@ -41,8 +28,8 @@ impl CategoryService {
         server_id: ServerId,
         page_size: i32,
         page_token: Option<String>,
-    ) -> Result<ListCategoriesResponse, Box<dyn Error>> {
-        let parent = format!("servers/{server_id}");
+    ) -> Result<ListCategoriesResponse, ClientError> {
+        let parent = ServerName::new(server_id).to_string();
 
         let request = ListCategoriesRequest {
             parent,
@@ -55,8 +42,12 @@ impl CategoryService {
         Ok(response.into_inner())
     }
 
-    pub async fn get_category(&mut self) -> Result<GetCategoryResponse, Box<dyn Error>> {
-        let name = format!("");
+    pub async fn get_category(
+        &mut self,
+        server_id: ServerId,
+        category_id: CategoryId,
+    ) -> Result<GetCategoryResponse, ClientError> {
+        let name = CategoryName::new(server_id, category_id).to_string();
         let request = GetCategoryRequest { name };
 
         let response = self.client.get_category(request).await?;
@@ -66,10 +57,11 @@ impl CategoryService {
 
     pub async fn create_category(
         &mut self,
+        server_id: ServerId,
         category: Category,
-    ) -> Result<Category, Box<dyn Error>> {
-        let parent = format!("");
-        let category_id = format!("");
+    ) -> Result<Category, ClientError> {
+        let parent = ServerName::new(server_id).to_string();
+        let category_id = CategoryId::new().to_string();
 
         let request = CreateCategoryRequest {
             parent,
@@ -82,10 +74,7 @@ impl CategoryService {
         Ok(response.into_inner())
     }
 
-    pub async fn update_category(
-        &mut self,
-        category: Category,
-    ) -> Result<Category, Box<dyn Error>> {
+    pub async fn update_category(&mut self, category: Category) -> Result<Category, ClientError> {
         let request = UpdateCategoryRequest {
             category: Some(category),
         };
@@ -95,12 +84,16 @@ impl CategoryService {
         Ok(response.into_inner())
     }
 
-    pub async fn delete_category(&mut self, category_id: CategoryId) -> Result<(), Box<dyn Error>> {
-        let name = format!("{}", category_id);
+    pub async fn delete_category(
+        &mut self,
+        server_id: ServerId,
+        category_id: CategoryId,
+    ) -> Result<(), ClientError> {
+        let name = CategoryName::new(server_id, category_id).to_string();
 
         let request = DeleteCategoryRequest { name };
 
-        let response = self.client.delete_category(request).await?;
+        self.client.delete_category(request).await?;
 
         Ok(())
     }
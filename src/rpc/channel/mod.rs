@@ -1,40 +1,27 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use super::interceptor::AuthMiddleware;
+use super::client::RpcChannel;
+use super::error::ClientError;
 use super::message::MessageId;
+use super::name::{ChannelName, MessageName, ServerName};
 use super::server::ServerId;
 use super::ycchat::v1::models::Channel;
-use super::ycchat::v1::services::auth::SignInResponse;
 use super::ycchat::v1::services::channel::channel_service_client::ChannelServiceClient;
 use super::ycchat::v1::services::channel::{
     CreateChannelRequest, DeleteChannelRequest, ListServerChannelsRequest,
     ListServerChannelsResponse, SpeechRequest, SpeechResponse, UpdateChannelRequest,
 };
-use tokio::sync::Mutex;
-use tonic::transport::Channel as TonicChannel;
-use tower::ServiceBuilder;
 use ulid::Ulid;
 
 pub type ChannelId = Ulid;
 
-struct ChannelService {
-    client: ChannelServiceClient<AuthMiddleware>,
+pub struct ChannelService {
+    client: ChannelServiceClient<RpcChannel>,
 }
 
 impl ChannelService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = TonicChannel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = ChannelServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
     pub async fn list_server_channels(
@@ -42,8 +29,8 @@ impl ChannelService {
         server_id: ServerId,
         page_size: i32,
         page_token: Option<String>,
-    ) -> Result<ListServerChannelsResponse, Box<dyn Error>> {
-        let parent = format!("servers/{}/channels", server_id);
+    ) -> Result<ListServerChannelsResponse, ClientError> {
+        let parent = ServerName::new(server_id).to_string();
 
         let request = ListServerChannelsRequest {
             parent,
@@ -56,7 +43,7 @@ impl ChannelService {
         Ok(res.into_inner())
     }
 
-    pub async fn create_channel(&mut self, channel: Channel) -> Result<Channel, Box<dyn Error>> {
+    pub async fn create_channel(&mut self, channel: Channel) -> Result<Channel, ClientError> {
         let request = CreateChannelRequest {
             channel: Some(channel),
         };
@@ -66,7 +53,7 @@ impl ChannelService {
         Ok(res.into_inner())
     }
 
-    pub async fn update_channel(&mut self, channel: Channel) -> Result<Channel, Box<dyn Error>> {
+    pub async fn update_channel(&mut self, channel: Channel) -> Result<Channel, ClientError> {
         let request = UpdateChannelRequest {
             channel: Some(channel),
         };
@@ -76,8 +63,8 @@ impl ChannelService {
         Ok(res.into_inner())
     }
 
-    pub async fn delete_channel(&mut self, channel_id: ChannelId) -> Result<(), Box<dyn Error>> {
-        let name = format!("channels/{}", channel_id);
+    pub async fn delete_channel(&mut self, channel_id: ChannelId) -> Result<(), ClientError> {
+        let name = ChannelName::new(channel_id).to_string();
 
         let request = DeleteChannelRequest { name };
 
@@ -90,10 +77,12 @@ impl ChannelService {
         &mut self,
         channel_id: ChannelId,
         content: String,
-        reply_to: MessageId,
-    ) -> Result<SpeechResponse, Box<dyn Error>> {
-        let name = format!("channels/{}", channel_id);
-        let reply_to = format!("messages/{}", reply_to);
+        reply_to: Option<MessageId>,
+    ) -> Result<SpeechResponse, ClientError> {
+        let name = ChannelName::new(channel_id).to_string();
+        let reply_to = reply_to
+            .map(|reply_to| MessageName::new(reply_to).to_string())
+            .unwrap_or_default();
 
         let request = SpeechRequest {
             name,
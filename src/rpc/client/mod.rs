@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tonic::transport::{Channel, Endpoint};
+use tower::retry::budget::Budget;
+use tower::retry::{Retry, RetryLayer};
+use tower::ServiceBuilder;
+
+use super::account::AccountService;
+use super::auth::AuthService;
+use super::category::CategoryService;
+use super::channel::ChannelService;
+use super::connect::ConnectService;
+use super::deadline::{Deadline, DeadlineLayer, Timeouts};
+use super::interceptor::reauth::{Credentials, Reauth};
+use super::interceptor::{Anonymous, AuthMiddleware};
+use super::me::MeUserService;
+use super::message::reaction::ReactionService;
+use super::message::MessageService;
+use super::retry::{self, BufferRequest, BufferRequestLayer, RetryPolicy};
+use super::server::ServerService;
+use super::server_member::ServerMemberService;
+use super::trace::{Trace, TraceLayer};
+use super::user::UserService;
+use super::ycchat::v1::services::auth::SignInResponse;
+
+/// Middleware stack every authenticated client is built on, outermost first.
+pub type RpcChannel = Deadline<BufferRequest<Retry<RetryPolicy, Trace<AuthMiddleware>>>>;
+
+/// Middleware stack of `AuthService`, which runs before there is a token to attach.
+pub type AuthChannel = Deadline<BufferRequest<Trace<Anonymous>>>;
+
+pub fn auth_channel(channel: Channel, timeouts: Timeouts) -> AuthChannel {
+    ServiceBuilder::new()
+        .layer(DeadlineLayer::new(timeouts))
+        .layer(BufferRequestLayer)
+        .layer(TraceLayer)
+        .service(Anonymous::new(channel))
+}
+
+/// Connection hub shared by every scene.
+///
+/// Owns a single HTTP/2 channel (multiplexed by tonic) and the auth state
+/// attached by `AuthMiddleware`; the typed clients it hands out are cheap
+/// handles onto that channel, so creating one never re-dials the server.
+#[derive(Clone)]
+pub struct RpcClient {
+    channel: Channel,
+    auth_state: Arc<Mutex<SignInResponse>>,
+    reauth: Reauth,
+    retry_budget: Arc<Budget>,
+    timeouts: Timeouts,
+}
+
+impl RpcClient {
+    pub fn new(endpoint: Endpoint, timeouts: Timeouts) -> Self {
+        let channel = endpoint.connect_lazy();
+
+        Self {
+            channel,
+            auth_state: Arc::new(Mutex::new(SignInResponse::default())),
+            reauth: Reauth::new(timeouts.clone()),
+            retry_budget: retry::new_budget(),
+            timeouts,
+        }
+    }
+
+    /// `credentials` are kept in memory so an expired token can be renewed by signing in again.
+    pub async fn set_auth(
+        &self,
+        sign_in_response: SignInResponse,
+        credentials: Option<Credentials>,
+    ) {
+        *self.auth_state.lock().await = sign_in_response;
+        self.reauth.set_credentials(credentials);
+    }
+
+    pub async fn clear_auth(&self) {
+        *self.auth_state.lock().await = SignInResponse::default();
+        self.reauth.set_credentials(None);
+    }
+
+    pub fn take_session_expired(&self) -> bool {
+        self.reauth.take_expired()
+    }
+
+    /// The renewed sign-in, once after the middleware replaced an expired token.
+    pub async fn take_refreshed_auth(&self) -> Option<SignInResponse> {
+        if !self.reauth.take_refreshed() {
+            return None;
+        }
+
+        Some(self.auth_state.lock().await.clone())
+    }
+
+    fn rpc_channel(&self) -> RpcChannel {
+        let auth_middleware = AuthMiddleware::new(
+            self.channel.clone(),
+            self.auth_state.clone(),
+            self.reauth.clone(),
+        );
+
+        ServiceBuilder::new()
+            .layer(DeadlineLayer::new(self.timeouts.clone()))
+            .layer(BufferRequestLayer)
+            .layer(RetryLayer::new(RetryPolicy::new(self.retry_budget.clone())))
+            .layer(TraceLayer)
+            .service(auth_middleware)
+    }
+
+    pub fn auth(&self) -> AuthService {
+        AuthService::new(auth_channel(self.channel.clone(), self.timeouts.clone()))
+    }
+
+    pub fn account(&self) -> AccountService {
+        AccountService::new(self.rpc_channel())
+    }
+
+    pub fn me(&self) -> MeUserService {
+        MeUserService::new(self.rpc_channel())
+    }
+
+    pub fn user(&self) -> UserService {
+        UserService::new(self.rpc_channel())
+    }
+
+    pub fn server(&self) -> ServerService {
+        ServerService::new(self.rpc_channel())
+    }
+
+    pub fn category(&self) -> CategoryService {
+        CategoryService::new(self.rpc_channel())
+    }
+
+    pub fn member(&self) -> ServerMemberService {
+        ServerMemberService::new(self.rpc_channel())
+    }
+
+    pub fn channel(&self) -> ChannelService {
+        ChannelService::new(self.rpc_channel())
+    }
+
+    pub fn message(&self) -> MessageService {
+        MessageService::new(self.rpc_channel())
+    }
+
+    pub fn reaction(&self) -> ReactionService {
+        ReactionService::new(self.rpc_channel())
+    }
+
+    pub fn connect(&self) -> ConnectService {
+        ConnectService::new(self.rpc_channel())
+    }
+}
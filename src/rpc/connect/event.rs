@@ -0,0 +1,47 @@
+use crate::rpc::channel::ChannelId;
+use crate::rpc::name::ChannelName;
+use crate::rpc::ycchat::v1::models::Message;
+use crate::rpc::ycchat::v1::services::connect::connect_response::Payload;
+use crate::rpc::ycchat::v1::services::connect::ConnectResponse;
+
+/// A push from the Connect stream, decoded into what the views act on.
+///
+/// The stream currently only carries messages; an edited message is pushed again
+/// under its original name with a newer `update_time`. Deletions, reactions and
+/// membership changes are not part of `ConnectResponse` yet.
+pub enum ServerEvent {
+    MessageCreated {
+        channel_id: ChannelId,
+        message: Message,
+    },
+    MessageUpdated {
+        channel_id: ChannelId,
+        message: Message,
+    },
+}
+
+impl ServerEvent {
+    /// Returns `None` for empty or malformed payloads.
+    pub fn decode(response: ConnectResponse) -> Option<Self> {
+        match response.payload? {
+            Payload::Message(message) => {
+                let channel_id = message.channel.parse::<ChannelName>().ok()?.channel_id;
+
+                let is_edited =
+                    message.update_time.is_some() && message.update_time != message.create_time;
+
+                if is_edited {
+                    Some(ServerEvent::MessageUpdated {
+                        channel_id,
+                        message,
+                    })
+                } else {
+                    Some(ServerEvent::MessageCreated {
+                        channel_id,
+                        message,
+                    })
+                }
+            }
+        }
+    }
+}
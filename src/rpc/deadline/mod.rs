@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use http::HeaderValue;
+use hyper::Body;
+use tonic::body::BoxBody;
+use tonic::Status;
+use tower::{Layer, Service};
+
+// Server-streaming calls stay open for the whole session, so no deadline is attached to them.
+const STREAMING_METHODS: &[&str] = &["/ycchat.v1.services.connect.ConnectService/Conn"];
+
+#[derive(Clone)]
+pub struct Timeouts {
+    pub default: Duration,
+    pub connect: Duration,
+    /// keyed by method name, e.g. `ListServers`
+    pub methods: HashMap<String, Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(10),
+            connect: Duration::from_secs(5),
+            methods: HashMap::new(),
+        }
+    }
+}
+
+impl Timeouts {
+    fn for_path(&self, path: &str) -> Option<Duration> {
+        if STREAMING_METHODS.contains(&path) {
+            return None;
+        }
+
+        let method = path.rsplit('/').next().unwrap_or(path);
+
+        Some(*self.methods.get(method).unwrap_or(&self.default))
+    }
+}
+
+/// Attaches a `grpc-timeout` header to every call and fails it locally with
+/// `DeadlineExceeded` when the response headers do not arrive in time.
+#[derive(Clone)]
+pub struct DeadlineLayer {
+    timeouts: Timeouts,
+}
+
+impl DeadlineLayer {
+    pub fn new(timeouts: Timeouts) -> Self {
+        Self { timeouts }
+    }
+}
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = Deadline<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Deadline::new(inner, self.timeouts.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct Deadline<S> {
+    inner: S,
+    timeouts: Timeouts,
+}
+
+impl<S> Deadline<S> {
+    pub fn new(inner: S, timeouts: Timeouts) -> Self {
+        Self { inner, timeouts }
+    }
+}
+
+impl<S> Service<http::Request<BoxBody>> for Deadline<S>
+where
+    S: Service<http::Request<BoxBody>, Response = http::Response<Body>> + Send,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<Body>;
+
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, mut req: http::Request<BoxBody>) -> Self::Future {
+        let timeout = self.timeouts.for_path(req.uri().path());
+
+        if let Some(timeout) = timeout {
+            req.headers_mut()
+                .insert("grpc-timeout", grpc_timeout_header(timeout));
+        }
+
+        let response = self.inner.call(req);
+
+        Box::pin(async move {
+            match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, response).await {
+                    Ok(response) => response.map_err(Into::into),
+                    Err(_) => Err(Status::deadline_exceeded("client deadline exceeded").into()),
+                },
+                None => response.await.map_err(Into::into),
+            }
+        })
+    }
+}
+
+// grpc-timeout allows at most 8 digits; milliseconds cover ~27 hours
+fn grpc_timeout_header(timeout: Duration) -> HeaderValue {
+    let millis = timeout.as_millis().min(99_999_999);
+
+    HeaderValue::from_str(&format!("{}m", millis)).unwrap()
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use tonic::{Code, Status};
+
+use super::name::ParseNameError;
+
+/// Error returned by every RPC wrapper in `crate::rpc`.
+///
+/// `Display` renders a message meant to be shown to the user as-is.
+#[derive(Debug)]
+pub enum ClientError {
+    /// rejected locally before anything was sent
+    Validation(String),
+    NotSignedIn,
+    Unauthenticated(String),
+    PermissionDenied(String),
+    NotFound(String),
+    AlreadyExists(String),
+    InvalidArgument(String),
+    /// a resource name returned by the server could not be parsed
+    InvalidName(ParseNameError),
+    Timeout,
+    Unavailable(String),
+    Transport(String),
+    Server(Box<Status>),
+}
+
+impl From<Status> for ClientError {
+    fn from(status: Status) -> Self {
+        let message = status.message().to_string();
+
+        match status.code() {
+            Code::Unauthenticated => ClientError::Unauthenticated(message),
+            Code::PermissionDenied => ClientError::PermissionDenied(message),
+            Code::NotFound => ClientError::NotFound(message),
+            Code::AlreadyExists => ClientError::AlreadyExists(message),
+            Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+                ClientError::InvalidArgument(message)
+            }
+            Code::DeadlineExceeded => ClientError::Timeout,
+            Code::Unavailable => ClientError::Unavailable(message),
+            _ => ClientError::Server(Box::new(status)),
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for ClientError {
+    fn from(err: tonic::transport::Error) -> Self {
+        ClientError::Transport(err.to_string())
+    }
+}
+
+impl From<ParseNameError> for ClientError {
+    fn from(err: ParseNameError) -> Self {
+        ClientError::InvalidName(err)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Validation(message) => write!(f, "{}", message),
+            ClientError::NotSignedIn => write!(f, "You are not signed in."),
+            ClientError::Unauthenticated(message) => {
+                write!(f, "Authentication failed{}", detail(message))
+            }
+            ClientError::PermissionDenied(message) => {
+                write!(
+                    f,
+                    "You do not have permission to do that{}",
+                    detail(message)
+                )
+            }
+            ClientError::NotFound(message) => write!(f, "Not found{}", detail(message)),
+            ClientError::AlreadyExists(message) => {
+                write!(f, "Already exists{}", detail(message))
+            }
+            ClientError::InvalidArgument(message) => {
+                write!(f, "Invalid request{}", detail(message))
+            }
+            ClientError::InvalidName(err) => write!(f, "Unexpected response: {}", err),
+            ClientError::Timeout => write!(f, "The server did not respond in time."),
+            ClientError::Unavailable(message) => {
+                write!(f, "Server is unavailable{}", detail(message))
+            }
+            ClientError::Transport(message) => {
+                write!(f, "Could not connect to the server: {}", message)
+            }
+            ClientError::Server(status) => write!(
+                f,
+                "Server error ({:?}){}",
+                status.code(),
+                detail(status.message())
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+fn detail(message: &str) -> String {
+    if message.is_empty() {
+        ".".to_string()
+    } else {
+        format!(": {}", message)
+    }
+}
@@ -1,28 +1,60 @@
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use http::HeaderValue;
+use hyper::body::{Bytes, HttpBody};
 use hyper::Body;
 use tokio::sync::Mutex;
-use tonic::body::BoxBody;
 use tonic::transport::Channel;
-use tower::Service;
+use tonic::{Code, Status};
+use tower::{Service, ServiceExt};
 
+use super::retry::grpc_status;
 use super::ycchat::v1::services::auth::SignInResponse;
 
+pub mod reauth;
+
+use reauth::Reauth;
+
+// refresh ahead of time so a request is not sent with a token that expires in flight
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+// These calls answer a wrong current password with `Unauthenticated`. Signing in again
+// would not change the answer, and without stored credentials it would end the session.
+const NO_REAUTH_METHODS: &[&str] = &[
+    "/ycchat.v1.services.account.AccountService/UpdatePassword",
+    "/ycchat.v1.services.account.AccountService/DeleteAccount",
+];
+
+/// Whether the request is worth replaying with a renewed token.
+fn needs_reauth(path: &str, jwt_token: &str, status: Option<Code>) -> bool {
+    !jwt_token.is_empty()
+        && status == Some(Code::Unauthenticated)
+        && !NO_REAUTH_METHODS.contains(&path)
+}
+
+#[derive(Clone)]
 pub struct AuthMiddleware {
     inner: Channel,
     auth_state: Arc<Mutex<SignInResponse>>,
+    reauth: Reauth,
 }
 
 impl AuthMiddleware {
-    pub fn new(inner: Channel, auth_state: Arc<Mutex<SignInResponse>>) -> Self {
-        Self { inner, auth_state }
+    pub fn new(inner: Channel, auth_state: Arc<Mutex<SignInResponse>>, reauth: Reauth) -> Self {
+        Self {
+            inner,
+            auth_state,
+            reauth,
+        }
     }
 }
 
-impl Service<hyper::Request<BoxBody>> for AuthMiddleware {
+// Requests arrive with their body already buffered (see `retry::BufferRequest`),
+// so they can be replayed after re-authentication.
+impl Service<hyper::Request<Bytes>> for AuthMiddleware {
     type Response = http::Response<Body>;
 
     type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -37,26 +69,133 @@ impl Service<hyper::Request<BoxBody>> for AuthMiddleware {
         self.inner.poll_ready(cx).map_err(Into::into)
     }
 
-    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+    fn call(&mut self, req: hyper::Request<Bytes>) -> Self::Future {
         // This is necessary because tonic internally uses `tower::buffer::Buffer`.
         // See https://github.com/tower-rs/tower/issues/547#issuecomment-767629149
         // for details on why this is necessary
         let clone = self.inner.clone();
-        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let inner = std::mem::replace(&mut self.inner, clone);
 
         let auth_state = Arc::clone(&self.auth_state);
+        let reauth = self.reauth.clone();
 
         Box::pin(async move {
-            let jwt_token = auth_state.lock().await.access_token.clone();
+            let (parts, body) = req.into_parts();
+
+            let mut jwt_token = auth_state.lock().await.access_token.clone();
 
-            let jwt_token =
-                HeaderValue::from_bytes(format!("Bearer {}", jwt_token).as_bytes()).unwrap();
+            if !jwt_token.is_empty() && reauth::expires_within(&jwt_token, REFRESH_MARGIN) {
+                if let Some(token) = reauth.refresh(inner.clone(), &auth_state, &jwt_token).await {
+                    jwt_token = token;
+                }
+            }
 
-            req.headers_mut().append("authorization", jwt_token);
+            let response = send(inner.clone(), &parts, body.clone(), Some(&jwt_token)).await?;
 
-            let response = inner.call(req).await?;
+            if !needs_reauth(parts.uri.path(), &jwt_token, grpc_status(&response)) {
+                return Ok(response);
+            }
 
-            Ok(response)
+            match reauth.refresh(inner.clone(), &auth_state, &jwt_token).await {
+                Some(token) => send(inner, &parts, body, Some(&token)).await,
+                None => Ok(response),
+            }
         })
     }
 }
+
+/// Sends requests without an `authorization` header, for the calls that obtain a token
+/// (sign-in, sign-up).
+#[derive(Clone)]
+pub struct Anonymous {
+    inner: Channel,
+}
+
+impl Anonymous {
+    pub fn new(inner: Channel) -> Self {
+        Self { inner }
+    }
+}
+
+impl Service<hyper::Request<Bytes>> for Anonymous {
+    type Response = http::Response<Body>;
+
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: hyper::Request<Bytes>) -> Self::Future {
+        // same as `AuthMiddleware::call`
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+
+            send(inner, &parts, body, None).await
+        })
+    }
+}
+
+async fn send(
+    mut inner: Channel,
+    parts: &http::request::Parts,
+    body: Bytes,
+    jwt_token: Option<&str>,
+) -> Result<http::Response<Body>, Box<dyn std::error::Error + Send + Sync>> {
+    let body = Body::from(body)
+        .map_err(|err| Status::from_error(Box::new(err)))
+        .boxed_unsync();
+
+    let mut req = hyper::Request::new(body);
+    *req.method_mut() = parts.method.clone();
+    *req.uri_mut() = parts.uri.clone();
+    *req.version_mut() = parts.version;
+    *req.headers_mut() = parts.headers.clone();
+
+    if let Some(jwt_token) = jwt_token {
+        let jwt_token = HeaderValue::from_str(&format!("Bearer {}", jwt_token))?;
+        req.headers_mut().insert("authorization", jwt_token);
+    }
+
+    let response = inner.ready().await?.call(req).await?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UPDATE_PASSWORD: &str = "/ycchat.v1.services.account.AccountService/UpdatePassword";
+    const LIST_SERVERS: &str = "/ycchat.v1.services.server.ServerService/ListServers";
+
+    #[test]
+    fn wrong_current_password_keeps_the_session() {
+        // refreshing here would fail for a restored session and sign the user out
+        assert!(!needs_reauth(
+            UPDATE_PASSWORD,
+            "token",
+            Some(Code::Unauthenticated)
+        ));
+    }
+
+    #[test]
+    fn expired_token_is_renewed() {
+        assert!(needs_reauth(
+            LIST_SERVERS,
+            "token",
+            Some(Code::Unauthenticated)
+        ));
+        assert!(!needs_reauth(LIST_SERVERS, "", Some(Code::Unauthenticated)));
+        assert!(!needs_reauth(LIST_SERVERS, "token", Some(Code::Ok)));
+    }
+}
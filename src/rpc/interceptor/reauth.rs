@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tonic::transport::Channel;
+
+use crate::rpc::auth::AuthService;
+use crate::rpc::client::auth_channel;
+use crate::rpc::deadline::Timeouts;
+use crate::rpc::ycchat::v1::services::auth::SignInResponse;
+
+#[derive(Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Re-authentication state shared by every `AuthMiddleware` of one `RpcClient`.
+#[derive(Clone)]
+pub struct Reauth {
+    credentials: Arc<std::sync::Mutex<Option<Credentials>>>,
+    expired: Arc<AtomicBool>,
+    /// set when `refresh` swapped in a new token, so it can be saved with the session
+    refreshed: Arc<AtomicBool>,
+    timeouts: Timeouts,
+}
+
+impl Reauth {
+    pub fn new(timeouts: Timeouts) -> Self {
+        Self {
+            credentials: Arc::new(std::sync::Mutex::new(None)),
+            expired: Arc::new(AtomicBool::new(false)),
+            refreshed: Arc::new(AtomicBool::new(false)),
+            timeouts,
+        }
+    }
+
+    pub fn set_credentials(&self, credentials: Option<Credentials>) {
+        *self.credentials.lock().unwrap() = credentials;
+        self.expired.store(false, Ordering::SeqCst);
+        self.refreshed.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns `true` once after a refresh failed, so the UI can ask the user to sign in again.
+    pub fn take_expired(&self) -> bool {
+        self.expired.swap(false, Ordering::SeqCst)
+    }
+
+    /// Returns `true` once after a refresh replaced the token.
+    pub fn take_refreshed(&self) -> bool {
+        self.refreshed.swap(false, Ordering::SeqCst)
+    }
+
+    /// Signs in again with the stored credentials and swaps the new token into `auth_state`.
+    ///
+    /// `stale_token` is the token the caller used; if another request already replaced it,
+    /// the current token is returned without another round trip.
+    pub async fn refresh(
+        &self,
+        channel: Channel,
+        auth_state: &Mutex<SignInResponse>,
+        stale_token: &str,
+    ) -> Option<String> {
+        let mut auth_state = auth_state.lock().await;
+
+        if auth_state.access_token != stale_token {
+            return Some(auth_state.access_token.clone());
+        }
+
+        let credentials = self.credentials.lock().unwrap().clone();
+
+        let response = match credentials {
+            Some(credentials) => AuthService::new(auth_channel(channel, self.timeouts.clone()))
+                .sign_in(credentials.username, credentials.password)
+                .await
+                .ok(),
+            None => None,
+        };
+
+        match response {
+            Some(response) => {
+                *auth_state = response;
+                self.refreshed.store(true, Ordering::SeqCst);
+                Some(auth_state.access_token.clone())
+            }
+            None => {
+                self.expired.store(true, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    exp: Option<u64>,
+}
+
+/// Whether `token` is a JWT whose `exp` claim falls within `margin` from now.
+/// Tokens that cannot be decoded are left for the server to judge.
+pub fn expires_within(token: &str, margin: Duration) -> bool {
+    let exp = token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+        .and_then(|payload| serde_json::from_slice::<Claims>(&payload).ok())
+        .and_then(|claims| claims.exp);
+
+    let exp = match exp {
+        Some(exp) => exp,
+        None => return false,
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    Duration::from_secs(exp) <= now + margin
+}
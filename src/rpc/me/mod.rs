@@ -1,41 +1,25 @@
-use std::{error::Error, sync::Arc};
-
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
-
 use super::{
-    interceptor::AuthMiddleware,
+    client::RpcChannel,
+    error::ClientError,
     user::UserId,
     ycchat::v1::{
         models::User,
-        services::{
-            auth::SignInResponse,
-            me::user::{me_user_service_client::MeUserServiceClient, GetMeRequest},
-        },
+        services::me::user::{me_user_service_client::MeUserServiceClient, GetMeRequest},
     },
 };
 
 pub struct MeUserService {
-    client: MeUserServiceClient<AuthMiddleware>,
+    client: MeUserServiceClient<RpcChannel>,
 }
 
 impl MeUserService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = MeUserServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
-    pub async fn get_user(&mut self) -> Result<User, Box<dyn Error>> {
+    pub async fn get_user(&mut self) -> Result<User, ClientError> {
         let request = GetMeRequest {};
         let response = self.client.get_me(request).await?;
 
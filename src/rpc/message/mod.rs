@@ -1,46 +1,51 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use super::interceptor::AuthMiddleware;
+use super::channel::ChannelId;
+use super::client::RpcChannel;
+use super::error::ClientError;
+use super::name::{ChannelName, MessageName};
 use super::ycchat::v1::models::Message;
-use super::ycchat::v1::services::auth::SignInResponse;
 use super::ycchat::v1::services::message::message_service_client::MessageServiceClient;
 use super::ycchat::v1::services::message::{
-    AcknowledgeMessageRequest, DeleteMessageRequest, UpdateMessageRequest,
+    AcknowledgeMessageRequest, DeleteMessageRequest, ListMessagesRequest, ListMessagesResponse,
+    UpdateMessageRequest,
 };
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
 use ulid::Ulid;
 
-mod reaction;
+pub mod reaction;
 
 pub type MessageId = Ulid;
 
 pub struct MessageService {
-    client: MessageServiceClient<AuthMiddleware>,
+    client: MessageServiceClient<RpcChannel>,
 }
 
 impl MessageService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
+    pub fn new(channel: RpcChannel) -> Self {
+        let client = MessageServiceClient::new(channel);
 
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
+        Self { client }
+    }
 
-        let channel = ServiceBuilder::new().service(auth_middleware);
+    pub async fn list_messages(
+        &mut self,
+        channel_id: ChannelId,
+        page_size: i32,
+        page_token: Option<String>,
+    ) -> Result<ListMessagesResponse, ClientError> {
+        let parent = ChannelName::new(channel_id).to_string();
+
+        let request = ListMessagesRequest {
+            parent,
+            page_size,
+            page_token,
+        };
 
-        let client = MessageServiceClient::new(channel);
+        let res = self.client.list_messages(request).await?;
 
-        Ok(Self { client })
+        Ok(res.into_inner())
     }
 
-    pub async fn acknowledge_message(
-        &mut self,
-        message_id: MessageId,
-    ) -> Result<(), Box<dyn Error>> {
-        let name = format!("message/{}", message_id);
+    pub async fn acknowledge_message(&mut self, message_id: MessageId) -> Result<(), ClientError> {
+        let name = MessageName::new(message_id).to_string();
 
         let request = AcknowledgeMessageRequest { name };
         self.client.acknowledge_message(request).await?;
@@ -48,7 +53,7 @@ impl MessageService {
         Ok(())
     }
 
-    pub async fn update_message(&mut self, message: Message) -> Result<Message, Box<dyn Error>> {
+    pub async fn update_message(&mut self, message: Message) -> Result<Message, ClientError> {
         let request = UpdateMessageRequest {
             message: Some(message),
         };
@@ -58,8 +63,8 @@ impl MessageService {
         Ok(res.into_inner())
     }
 
-    pub async fn delete_message(&mut self, message_id: MessageId) -> Result<(), Box<dyn Error>> {
-        let name = format!("message/{}", message_id);
+    pub async fn delete_message(&mut self, message_id: MessageId) -> Result<(), ClientError> {
+        let name = MessageName::new(message_id).to_string();
 
         let request = DeleteMessageRequest { name };
 
@@ -1,16 +1,11 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use crate::rpc::interceptor::AuthMiddleware;
+use crate::rpc::client::RpcChannel;
+use crate::rpc::error::ClientError;
+use crate::rpc::name::{MessageName, ReactionName};
 use crate::rpc::ycchat::v1::models::Reaction;
-use crate::rpc::ycchat::v1::services::auth::SignInResponse;
 use crate::rpc::ycchat::v1::services::message::reaction_service_client::ReactionServiceClient;
 use crate::rpc::ycchat::v1::services::message::{
     AddReactionRequest, DeleteReactionRequest, ListReactionsRequest, ListReactionsResponse,
 };
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
 use ulid::Ulid;
 
 use super::MessageId;
@@ -18,22 +13,14 @@ use super::MessageId;
 pub type ReactionId = Ulid;
 
 pub struct ReactionService {
-    client: ReactionServiceClient<AuthMiddleware>,
+    client: ReactionServiceClient<RpcChannel>,
 }
 
 impl ReactionService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = ReactionServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
     pub async fn list_reactions(
@@ -41,8 +28,8 @@ impl ReactionService {
         message_id: MessageId,
         page_size: i32,
         page_token: Option<String>,
-    ) -> Result<ListReactionsResponse, Box<dyn Error>> {
-        let parent = format!("messages/{}", message_id);
+    ) -> Result<ListReactionsResponse, ClientError> {
+        let parent = MessageName::new(message_id).to_string();
 
         let request = ListReactionsRequest {
             parent,
@@ -58,18 +45,25 @@ impl ReactionService {
     pub async fn add_reaction(
         &mut self,
         message_id: MessageId,
-    ) -> Result<Reaction, Box<dyn Error>> {
-        let parent = format!("messages/{}", message_id);
+        emoji: String,
+    ) -> Result<Reaction, ClientError> {
+        let parent = MessageName::new(message_id).to_string();
 
-        let request = AddReactionRequest { parent };
+        let request = AddReactionRequest {
+            parent,
+            reaction: Some(Reaction {
+                emoji,
+                ..Default::default()
+            }),
+        };
 
         let res = self.client.add_reaction(request).await?;
 
         Ok(res.into_inner())
     }
 
-    pub async fn delete_reaction(&mut self, reaction_id: ReactionId) -> Result<(), Box<dyn Error>> {
-        let name = format!("reactions/{}", reaction_id);
+    pub async fn delete_reaction(&mut self, reaction: ReactionName) -> Result<(), ClientError> {
+        let name = reaction.to_string();
         let request = DeleteReactionRequest { name };
 
         self.client.delete_reaction(request).await?;
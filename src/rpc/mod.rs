@@ -2,14 +2,20 @@ pub mod account;
 pub mod auth;
 pub mod category;
 pub mod channel;
+pub mod client;
 pub mod connect;
+pub mod deadline;
+pub mod error;
 pub mod me;
 pub mod message;
+pub mod name;
+pub mod retry;
 pub mod server;
 pub mod server_member;
+pub mod trace;
 pub mod user;
 
-mod interceptor;
+pub mod interceptor;
 
 pub mod ycchat {
     pub mod v1 {
@@ -0,0 +1,355 @@
+//! Canonical resource names (`servers/{server}`, `servers/{server}/members/{user}`, ...).
+//!
+//! Every request built in `crate::rpc` formats its `name`/`parent` through these types,
+//! and names returned by the server are parsed back with `FromStr`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ulid::Ulid;
+
+use super::category::CategoryId;
+use super::channel::ChannelId;
+use super::message::reaction::ReactionId;
+use super::message::MessageId;
+use super::server::ServerId;
+use super::user::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNameError {
+    name: String,
+    expected: &'static str,
+}
+
+impl fmt::Display for ParseNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is not a {} name", self.name, self.expected)
+    }
+}
+
+impl std::error::Error for ParseNameError {}
+
+/// Splits `name` into ids, requiring exactly `collections[i]/{id}` for every collection.
+fn parse_ids<const N: usize>(
+    name: &str,
+    collections: [&'static str; N],
+    expected: &'static str,
+) -> Result<[Ulid; N], ParseNameError> {
+    let err = || ParseNameError {
+        name: name.to_string(),
+        expected,
+    };
+
+    let mut segments = name.split('/');
+    let mut ids = [Ulid::nil(); N];
+
+    for (collection, id) in collections.iter().zip(ids.iter_mut()) {
+        if segments.next() != Some(collection) {
+            return Err(err());
+        }
+
+        *id = segments
+            .next()
+            .and_then(|id| Ulid::from_string(id).ok())
+            .ok_or_else(err)?;
+    }
+
+    if segments.next().is_some() {
+        return Err(err());
+    }
+
+    Ok(ids)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserName {
+    pub user_id: UserId,
+}
+
+impl UserName {
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id }
+    }
+}
+
+impl fmt::Display for UserName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "users/{}", self.user_id)
+    }
+}
+
+impl FromStr for UserName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [user_id] = parse_ids(name, ["users"], "user")?;
+        Ok(Self { user_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServerName {
+    pub server_id: ServerId,
+}
+
+impl ServerName {
+    pub fn new(server_id: ServerId) -> Self {
+        Self { server_id }
+    }
+}
+
+impl fmt::Display for ServerName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "servers/{}", self.server_id)
+    }
+}
+
+impl FromStr for ServerName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [server_id] = parse_ids(name, ["servers"], "server")?;
+        Ok(Self { server_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemberName {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+}
+
+impl MemberName {
+    pub fn new(server_id: ServerId, user_id: UserId) -> Self {
+        Self { server_id, user_id }
+    }
+}
+
+impl fmt::Display for MemberName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "servers/{}/members/{}", self.server_id, self.user_id)
+    }
+}
+
+impl FromStr for MemberName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [server_id, user_id] = parse_ids(name, ["servers", "members"], "server member")?;
+        Ok(Self { server_id, user_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CategoryName {
+    pub server_id: ServerId,
+    pub category_id: CategoryId,
+}
+
+impl CategoryName {
+    pub fn new(server_id: ServerId, category_id: CategoryId) -> Self {
+        Self {
+            server_id,
+            category_id,
+        }
+    }
+}
+
+impl fmt::Display for CategoryName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "servers/{}/categories/{}",
+            self.server_id, self.category_id
+        )
+    }
+}
+
+impl FromStr for CategoryName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [server_id, category_id] = parse_ids(name, ["servers", "categories"], "category")?;
+        Ok(Self {
+            server_id,
+            category_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelName {
+    pub channel_id: ChannelId,
+}
+
+impl ChannelName {
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self { channel_id }
+    }
+}
+
+impl fmt::Display for ChannelName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "channels/{}", self.channel_id)
+    }
+}
+
+impl FromStr for ChannelName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [channel_id] = parse_ids(name, ["channels"], "channel")?;
+        Ok(Self { channel_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageName {
+    pub message_id: MessageId,
+}
+
+impl MessageName {
+    pub fn new(message_id: MessageId) -> Self {
+        Self { message_id }
+    }
+}
+
+impl fmt::Display for MessageName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "messages/{}", self.message_id)
+    }
+}
+
+impl FromStr for MessageName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [message_id] = parse_ids(name, ["messages"], "message")?;
+        Ok(Self { message_id })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReactionName {
+    pub message_id: MessageId,
+    pub reaction_id: ReactionId,
+}
+
+impl ReactionName {
+    pub fn new(message_id: MessageId, reaction_id: ReactionId) -> Self {
+        Self {
+            message_id,
+            reaction_id,
+        }
+    }
+}
+
+impl fmt::Display for ReactionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "messages/{}/reactions/{}",
+            self.message_id, self.reaction_id
+        )
+    }
+}
+
+impl FromStr for ReactionName {
+    type Err = ParseNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let [message_id, reaction_id] = parse_ids(name, ["messages", "reactions"], "reaction")?;
+        Ok(Self {
+            message_id,
+            reaction_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T>(name: T, expected: &str)
+    where
+        T: fmt::Display + FromStr<Err = ParseNameError> + PartialEq + fmt::Debug,
+    {
+        assert_eq!(name.to_string(), expected);
+        assert_eq!(expected.parse::<T>().unwrap(), name);
+    }
+
+    #[test]
+    fn user_name_round_trip() {
+        let user_id = Ulid::new();
+        round_trip(UserName::new(user_id), &format!("users/{user_id}"));
+    }
+
+    #[test]
+    fn server_name_round_trip() {
+        let server_id = Ulid::new();
+        round_trip(ServerName::new(server_id), &format!("servers/{server_id}"));
+    }
+
+    #[test]
+    fn member_name_round_trip() {
+        let (server_id, user_id) = (Ulid::new(), Ulid::new());
+        round_trip(
+            MemberName::new(server_id, user_id),
+            &format!("servers/{server_id}/members/{user_id}"),
+        );
+    }
+
+    #[test]
+    fn category_name_round_trip() {
+        let (server_id, category_id) = (Ulid::new(), Ulid::new());
+        round_trip(
+            CategoryName::new(server_id, category_id),
+            &format!("servers/{server_id}/categories/{category_id}"),
+        );
+    }
+
+    #[test]
+    fn channel_name_round_trip() {
+        let channel_id = Ulid::new();
+        round_trip(
+            ChannelName::new(channel_id),
+            &format!("channels/{channel_id}"),
+        );
+    }
+
+    #[test]
+    fn message_name_round_trip() {
+        let message_id = Ulid::new();
+        round_trip(
+            MessageName::new(message_id),
+            &format!("messages/{message_id}"),
+        );
+    }
+
+    #[test]
+    fn reaction_name_round_trip() {
+        let (message_id, reaction_id) = (Ulid::new(), Ulid::new());
+        round_trip(
+            ReactionName::new(message_id, reaction_id),
+            &format!("messages/{message_id}/reactions/{reaction_id}"),
+        );
+    }
+
+    #[test]
+    fn rejects_non_canonical_names() {
+        let id = Ulid::new();
+
+        assert!(format!("/servers/{id}").parse::<ServerName>().is_err());
+        assert!(format!("servers/{id}/").parse::<ServerName>().is_err());
+        assert!(format!("servers/{id}/channels")
+            .parse::<ServerName>()
+            .is_err());
+        assert!(format!("message/{id}").parse::<MessageName>().is_err());
+        assert!(format!("user/{id}").parse::<UserName>().is_err());
+        assert!("servers/not-a-ulid".parse::<ServerName>().is_err());
+        assert!(format!("servers/{id}").parse::<ChannelName>().is_err());
+        assert!(format!("servers/{id}/members")
+            .parse::<MemberName>()
+            .is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::body::Bytes;
+use hyper::Body;
+use rand::Rng;
+use tonic::body::BoxBody;
+use tonic::Code;
+use tower::retry::budget::Budget;
+use tower::retry::Policy;
+use tower::{Layer, Service, ServiceExt};
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_millis(100);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+// Methods that can be sent twice without side effects. Anything not listed here
+// (speech, create_server, sign_in, ...) is never retried.
+const IDEMPOTENT_METHODS: &[&str] = &[
+    "/ycchat.v1.services.me.user.MeUserService/GetMe",
+    "/ycchat.v1.services.user.UserService/GetUser",
+    "/ycchat.v1.services.server.ServerService/ListServers",
+    "/ycchat.v1.services.server.ServerService/GetServer",
+    "/ycchat.v1.services.server.category.CategoryService/ListCategories",
+    "/ycchat.v1.services.server.category.CategoryService/GetCategory",
+    "/ycchat.v1.services.server.member.ServerMemberService/ListServerMembers",
+    "/ycchat.v1.services.server.member.ServerMemberService/GetServerMember",
+    "/ycchat.v1.services.channel.ChannelService/ListServerChannels",
+    "/ycchat.v1.services.message.MessageService/ListMessages",
+    "/ycchat.v1.services.message.MessageService/AcknowledgeMessage",
+    "/ycchat.v1.services.message.ReactionService/ListReactions",
+];
+
+pub fn is_idempotent(path: &str) -> bool {
+    IDEMPOTENT_METHODS.contains(&path)
+}
+
+pub fn new_budget() -> Arc<Budget> {
+    // at least 10 retries per second, plus 20% of recent requests
+    Arc::new(Budget::new(Duration::from_secs(10), 10, 0.2))
+}
+
+/// Retries idempotent calls that failed with `Unavailable` or a transport error,
+/// sleeping with jittered exponential backoff between attempts.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    attempts: u32,
+    budget: Arc<Budget>,
+}
+
+impl RetryPolicy {
+    pub fn new(budget: Arc<Budget>) -> Self {
+        Self {
+            attempts: 0,
+            budget,
+        }
+    }
+}
+
+impl<E> Policy<http::Request<Bytes>, http::Response<Body>, E> for RetryPolicy {
+    type Future = Pin<Box<dyn Future<Output = Self> + Send>>;
+
+    fn retry(
+        &self,
+        req: &http::Request<Bytes>,
+        result: Result<&http::Response<Body>, &E>,
+    ) -> Option<Self::Future> {
+        if self.attempts + 1 >= MAX_ATTEMPTS || !is_idempotent(req.uri().path()) {
+            return None;
+        }
+
+        let is_transient = match result {
+            Ok(response) => grpc_status(response) == Some(Code::Unavailable),
+            Err(_) => true,
+        };
+
+        if !is_transient || self.budget.withdraw().is_err() {
+            return None;
+        }
+
+        let delay = backoff(BASE_DELAY, MAX_DELAY, self.attempts);
+        let next = Self {
+            attempts: self.attempts + 1,
+            budget: self.budget.clone(),
+        };
+
+        Some(Box::pin(async move {
+            tokio::time::sleep(delay).await;
+            next
+        }))
+    }
+
+    fn clone_request(&self, req: &http::Request<Bytes>) -> Option<http::Request<Bytes>> {
+        if self.attempts == 0 {
+            self.budget.deposit();
+        }
+
+        let mut clone = http::Request::new(req.body().clone());
+        *clone.method_mut() = req.method().clone();
+        *clone.uri_mut() = req.uri().clone();
+        *clone.version_mut() = req.version();
+        *clone.headers_mut() = req.headers().clone();
+
+        Some(clone)
+    }
+}
+
+/// Exponential backoff capped at `max`, with equal jitter: half of the delay,
+/// plus a random share of the other half.
+pub fn backoff(base: Duration, max: Duration, attempt: u32) -> Duration {
+    let ceiling = base.saturating_mul(2u32.saturating_pow(attempt)).min(max);
+
+    let half = ceiling / 2;
+    half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+}
+
+// unary errors arrive as trailers-only responses, so the status is in the headers
+pub fn grpc_status(response: &http::Response<Body>) -> Option<Code> {
+    response
+        .headers()
+        .get("grpc-status")
+        .and_then(|status| status.to_str().ok())
+        .and_then(|status| status.parse::<i32>().ok())
+        .map(Code::from_i32)
+}
+
+/// Collects the request body into `Bytes` so the layers below can replay the request.
+#[derive(Clone)]
+pub struct BufferRequestLayer;
+
+impl<S> Layer<S> for BufferRequestLayer {
+    type Service = BufferRequest<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BufferRequest { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct BufferRequest<S> {
+    inner: S,
+}
+
+impl<S> Service<http::Request<BoxBody>> for BufferRequest<S>
+where
+    S: Service<
+            http::Request<Bytes>,
+            Response = http::Response<Body>,
+            Error = Box<dyn std::error::Error + Send + Sync>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    type Response = http::Response<Body>;
+
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<BoxBody>) -> Self::Future {
+        // take the service that was driven to readiness, see `AuthMiddleware::call`
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+
+            inner
+                .ready()
+                .await?
+                .call(http::Request::from_parts(parts, body))
+                .await
+        })
+    }
+}
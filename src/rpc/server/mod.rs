@@ -1,16 +1,11 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
 use ulid::Ulid;
 
 use crate::rpc::ycchat::v1::services::server::EnterServerRequest;
 
-use super::interceptor::AuthMiddleware;
+use super::client::RpcChannel;
+use super::error::ClientError;
+use super::name::ServerName;
 use super::ycchat::v1::models::{Attachment, Server, ServerMember};
-use super::ycchat::v1::services::auth::SignInResponse;
 use super::ycchat::v1::services::server::server_service_client::ServerServiceClient;
 use super::ycchat::v1::services::server::{
     CreateServerRequest, DeleteServerRequest, GetServerRequest, LeaveServerRequest,
@@ -20,25 +15,17 @@ use super::ycchat::v1::services::server::{
 pub type ServerId = Ulid;
 
 pub struct ServerService {
-    client: ServerServiceClient<AuthMiddleware>,
+    client: ServerServiceClient<RpcChannel>,
 }
 
 impl ServerService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = ServerServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
-    pub async fn create_server(&mut self, server: Server) -> Result<Server, Box<dyn Error>> {
+    pub async fn create_server(&mut self, server: Server) -> Result<Server, ClientError> {
         let request = CreateServerRequest {
             server: Some(server),
         };
@@ -52,7 +39,7 @@ impl ServerService {
         &mut self,
         page_size: i32,
         page_token: Option<String>,
-    ) -> Result<ListServersResponse, Box<dyn Error>> {
+    ) -> Result<ListServersResponse, ClientError> {
         let request = ListServersRequest {
             page_size,
             page_token,
@@ -63,8 +50,8 @@ impl ServerService {
         Ok(response.into_inner())
     }
 
-    pub async fn get_server(&mut self, server_id: ServerId) -> Result<Server, Box<dyn Error>> {
-        let name = format!("servers/{}", server_id);
+    pub async fn get_server(&mut self, server_id: ServerId) -> Result<Server, ClientError> {
+        let name = ServerName::new(server_id).to_string();
         let request = GetServerRequest { name };
 
         let response = self.client.get_server(request).await?;
@@ -72,7 +59,7 @@ impl ServerService {
         Ok(response.into_inner())
     }
 
-    pub async fn update_server(&mut self, server: Server) -> Result<Server, Box<dyn Error>> {
+    pub async fn update_server(&mut self, server: Server) -> Result<Server, ClientError> {
         let request = UpdateServerRequest {
             server: Some(server),
         };
@@ -82,8 +69,8 @@ impl ServerService {
         Ok(response.into_inner())
     }
 
-    pub async fn delete_server(&mut self, server_id: ServerId) -> Result<(), Box<dyn Error>> {
-        let name = format!("servers/{}", server_id);
+    pub async fn delete_server(&mut self, server_id: ServerId) -> Result<(), ClientError> {
+        let name = ServerName::new(server_id).to_string();
 
         let request = DeleteServerRequest { name };
 
@@ -98,8 +85,8 @@ impl ServerService {
         display_name: String,
         description: String,
         avartar: Option<Attachment>,
-    ) -> Result<ServerMember, Box<dyn Error>> {
-        let name = format!("servers/{}", server_id);
+    ) -> Result<ServerMember, ClientError> {
+        let name = ServerName::new(server_id).to_string();
 
         let request = EnterServerRequest {
             name,
@@ -113,8 +100,8 @@ impl ServerService {
         Ok(response.into_inner())
     }
 
-    pub async fn leave_server(&mut self, server_id: ServerId) -> Result<(), Box<dyn Error>> {
-        let name = format!("servers/{}", server_id);
+    pub async fn leave_server(&mut self, server_id: ServerId) -> Result<(), ClientError> {
+        let name = ServerName::new(server_id).to_string();
 
         let request = LeaveServerRequest { name };
 
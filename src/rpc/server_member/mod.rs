@@ -1,15 +1,9 @@
-use std::error::Error;
-use std::sync::Arc;
-
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
-use tower::ServiceBuilder;
-
-use super::interceptor::AuthMiddleware;
+use super::client::RpcChannel;
+use super::error::ClientError;
+use super::name::{MemberName, ServerName};
 use super::server::ServerId;
 use super::user::UserId;
 use super::ycchat::v1::models::ServerMember;
-use super::ycchat::v1::services::auth::SignInResponse;
 use super::ycchat::v1::services::server::member::server_member_service_client::ServerMemberServiceClient;
 use super::ycchat::v1::services::server::member::{
     GetServerMemberRequest, ListServerMembersRequest, ListServerMembersResponse,
@@ -20,22 +14,14 @@ use super::ycchat::v1::services::server::member::{
 // };
 
 pub struct ServerMemberService {
-    client: ServerMemberServiceClient<AuthMiddleware>,
+    client: ServerMemberServiceClient<RpcChannel>,
 }
 
 impl ServerMemberService {
-    pub async fn new(auth_state: Arc<Mutex<SignInResponse>>) -> Result<Self, Box<dyn Error>> {
-        let channel = Channel::from_static("http://127.0.0.1:50051")
-            .connect()
-            .await?;
-
-        let auth_middleware = AuthMiddleware::new(channel.clone(), auth_state);
-
-        let channel = ServiceBuilder::new().service(auth_middleware);
-
+    pub fn new(channel: RpcChannel) -> Self {
         let client = ServerMemberServiceClient::new(channel);
 
-        Ok(Self { client })
+        Self { client }
     }
 
     pub async fn list_server_members(
@@ -43,8 +29,8 @@ impl ServerMemberService {
         server_id: ServerId,
         page_size: i32,
         page_token: Option<String>,
-    ) -> Result<ListServerMembersResponse, Box<dyn Error>> {
-        let parent = format!("servers/{server_id}");
+    ) -> Result<ListServerMembersResponse, ClientError> {
+        let parent = ServerName::new(server_id).to_string();
 
         let request = ListServerMembersRequest {
             parent,
@@ -61,8 +47,8 @@ impl ServerMemberService {
         &mut self,
         server_id: ServerId,
         user_id: UserId,
-    ) -> Result<ServerMember, Box<dyn Error>> {
-        let name = format!("/servers/{server_id}/members/{user_id}");
+    ) -> Result<ServerMember, ClientError> {
+        let name = MemberName::new(server_id, user_id).to_string();
         let request = GetServerMemberRequest { name };
 
         let response = self.client.get_server_member(request).await?;
@@ -0,0 +1,304 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Instant;
+
+use http::{HeaderMap, HeaderValue};
+use hyper::body::{Bytes, HttpBody};
+use hyper::Body;
+use tonic::Code;
+use tower::{Layer, Service};
+
+use super::retry::grpc_status;
+
+// top-level collections of the ycchat resource names
+const COLLECTIONS: &[&str] = &["users/", "servers/", "channels/", "messages/"];
+
+// requests of these services carry passwords, so their bodies are never looked into
+const SECRET_SERVICES: &[&str] = &[
+    "/ycchat.v1.services.auth.AuthService/",
+    "/ycchat.v1.services.account.AccountService/",
+];
+
+/// Logs one event per RPC attempt: method, resource name, status code, latency and
+/// request/response sizes. Headers are only logged at `trace` level, with credentials redacted.
+#[derive(Clone)]
+pub struct TraceLayer;
+
+impl<S> Layer<S> for TraceLayer {
+    type Service = Trace<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Trace { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct Trace<S> {
+    inner: S,
+}
+
+impl<S> Service<http::Request<Bytes>> for Trace<S>
+where
+    S: Service<
+        http::Request<Bytes>,
+        Response = http::Response<Body>,
+        Error = Box<dyn std::error::Error + Send + Sync>,
+    >,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<Body>;
+
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<Bytes>) -> Self::Future {
+        let method = req.uri().path();
+        let resource = if SECRET_SERVICES
+            .iter()
+            .any(|service| method.starts_with(service))
+        {
+            None
+        } else {
+            resource_name(req.body())
+        };
+
+        let call = CallInfo {
+            method: method.to_string(),
+            resource: resource.unwrap_or_default(),
+            request_bytes: req.body().len(),
+            start: Instant::now(),
+        };
+
+        tracing::trace!(method = %call.method, headers = ?redact(req.headers()), "rpc request");
+
+        let response = self.inner.call(req);
+
+        Box::pin(async move {
+            let response = match response.await {
+                Ok(response) => response,
+                Err(err) => {
+                    call.log(None, 0, Some(&err.to_string()));
+                    return Err(err);
+                }
+            };
+
+            // trailers-only response: the call already finished
+            if let Some(code) = grpc_status(&response) {
+                call.log(Some(code), 0, None);
+                return Ok(response);
+            }
+
+            // the status arrives in the trailers, so the body is relayed to see it
+            let (parts, body) = response.into_parts();
+            let (sender, relayed) = Body::channel();
+
+            tokio::spawn(relay(body, sender, call));
+
+            Ok(http::Response::from_parts(parts, relayed))
+        })
+    }
+}
+
+struct CallInfo {
+    method: String,
+    resource: String,
+    request_bytes: usize,
+    start: Instant,
+}
+
+impl CallInfo {
+    fn log(&self, code: Option<Code>, response_bytes: usize, error: Option<&str>) {
+        let latency_ms = self.start.elapsed().as_millis() as u64;
+        let status = code.map(|code| format!("{:?}", code)).unwrap_or_default();
+
+        match (code, error) {
+            (Some(Code::Ok), None) => tracing::info!(
+                method = %self.method,
+                resource = %self.resource,
+                status,
+                latency_ms,
+                request_bytes = self.request_bytes,
+                response_bytes,
+                "rpc"
+            ),
+            _ => tracing::warn!(
+                method = %self.method,
+                resource = %self.resource,
+                status,
+                latency_ms,
+                request_bytes = self.request_bytes,
+                response_bytes,
+                error = error.unwrap_or_default(),
+                "rpc failed"
+            ),
+        }
+    }
+}
+
+async fn relay(mut body: Body, mut sender: hyper::body::Sender, call: CallInfo) {
+    let mut response_bytes = 0;
+
+    while let Some(chunk) = body.data().await {
+        match chunk {
+            Ok(chunk) => {
+                response_bytes += chunk.len();
+
+                if sender.send_data(chunk).await.is_err() {
+                    // the caller dropped the response (e.g. a closed stream)
+                    call.log(None, response_bytes, Some("response dropped"));
+                    return;
+                }
+            }
+            Err(err) => {
+                call.log(None, response_bytes, Some(&err.to_string()));
+                sender.abort();
+                return;
+            }
+        }
+    }
+
+    match body.trailers().await {
+        Ok(Some(trailers)) => {
+            let code = trailers
+                .get("grpc-status")
+                .and_then(|status| status.to_str().ok())
+                .and_then(|status| status.parse::<i32>().ok())
+                .map(Code::from_i32);
+
+            call.log(code, response_bytes, None);
+
+            let _ = sender.send_trailers(trailers).await;
+        }
+        Ok(None) => call.log(None, response_bytes, Some("missing trailers")),
+        Err(err) => {
+            call.log(None, response_bytes, Some(&err.to_string()));
+            sender.abort();
+        }
+    }
+}
+
+fn redact(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+
+    if headers.contains_key("authorization") {
+        headers.insert("authorization", HeaderValue::from_static("<redacted>"));
+    }
+
+    headers
+}
+
+/// Best-effort extraction of the `name`/`parent` field from a gRPC request frame.
+///
+/// Every request in the ycchat API carries its resource name as field 1, either directly
+/// or inside the resource being created/updated, so this looks at field 1 up to one level deep.
+fn resource_name(frame: &[u8]) -> Option<String> {
+    // 1 byte compression flag + 4 bytes message length
+    let message = frame.get(5..)?;
+    let field = first_field(message)?;
+
+    if let Some(name) = as_resource_name(field) {
+        return Some(name);
+    }
+
+    first_field(field).and_then(as_resource_name)
+}
+
+fn as_resource_name(field: &[u8]) -> Option<String> {
+    let name = std::str::from_utf8(field).ok()?;
+
+    let is_resource_name = COLLECTIONS
+        .iter()
+        .any(|collection| name.starts_with(collection))
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '/' || c == '-' || c == '_');
+
+    is_resource_name.then(|| name.to_string())
+}
+
+/// Returns the payload of field 1 when it is length-delimited.
+fn first_field(message: &[u8]) -> Option<&[u8]> {
+    // tag: field number 1, wire type 2
+    if *message.first()? != 0x0a {
+        return None;
+    }
+
+    let mut len: usize = 0;
+    let mut offset = 1;
+
+    for shift in (0..35).step_by(7) {
+        let byte = *message.get(offset)?;
+        offset += 1;
+        len |= ((byte & 0x7f) as usize) << shift;
+
+        if byte & 0x80 == 0 {
+            return message.get(offset..offset.checked_add(len)?);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0];
+        frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        frame.extend_from_slice(message);
+        frame
+    }
+
+    fn string_field(value: &[u8]) -> Vec<u8> {
+        let mut field = vec![0x0a, value.len() as u8];
+        field.extend_from_slice(value);
+        field
+    }
+
+    #[test]
+    fn resource_name_from_name_field() {
+        let message = string_field(b"servers/01HK3F6GZ3C8Y2Q9W7V6T5R4E3");
+
+        assert_eq!(
+            resource_name(&frame(&message)).as_deref(),
+            Some("servers/01HK3F6GZ3C8Y2Q9W7V6T5R4E3")
+        );
+    }
+
+    #[test]
+    fn resource_name_from_nested_resource() {
+        let message = string_field(&string_field(b"channels/01HK3F6GZ3C8Y2Q9W7V6T5R4E3"));
+
+        assert_eq!(
+            resource_name(&frame(&message)).as_deref(),
+            Some("channels/01HK3F6GZ3C8Y2Q9W7V6T5R4E3")
+        );
+    }
+
+    #[test]
+    fn plain_strings_are_not_resource_names() {
+        // e.g. SignInRequest.username
+        let message = string_field(b"alice");
+
+        assert_eq!(resource_name(&frame(&message)), None);
+        assert_eq!(resource_name(&frame(&[])), None);
+    }
+
+    #[test]
+    fn secrets_are_not_resource_names() {
+        // e.g. UpdatePasswordRequest.current_password
+        let message = string_field(b"abc/def12");
+
+        assert_eq!(resource_name(&frame(&message)), None);
+    }
+}
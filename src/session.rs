@@ -0,0 +1,303 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::rpc::ycchat::v1::services::auth::SignInResponse;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// A sign-in restored from (or saved to) the session file.
+pub struct Session {
+    pub username: String,
+    pub sign_in_response: SignInResponse,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Io(io::Error),
+    /// the file is not a session file this version can read
+    Format(String),
+    /// wrong passphrase, or the file was modified
+    Decrypt,
+    /// the file is encrypted but no passphrase was given
+    PassphraseRequired,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Io(err) => write!(f, "{}", err),
+            SessionError::Format(err) => write!(f, "unreadable session file: {}", err),
+            SessionError::Decrypt => write!(f, "wrong passphrase"),
+            SessionError::PassphraseRequired => {
+                write!(f, "the session is encrypted (use --encrypt-session)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<io::Error> for SessionError {
+    fn from(err: io::Error) -> Self {
+        SessionError::Io(err)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+enum SessionFile {
+    Plain {
+        session: StoredSession,
+    },
+    /// `StoredSession` as JSON, sealed with XChaCha20-Poly1305 under an Argon2id key
+    Encrypted {
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    /// a session is only restored against the server it was created on
+    endpoint: String,
+    username: String,
+    user_id: String,
+    access_token: String,
+}
+
+/// The session file of the current profile.
+pub struct SessionStore {
+    path: PathBuf,
+    endpoint: String,
+    passphrase: Option<String>,
+}
+
+impl SessionStore {
+    /// `None` when there is no data directory to keep the session in.
+    pub fn new(config: &Config, passphrase: Option<String>) -> Option<Self> {
+        Some(Self {
+            path: config.session_path.clone()?,
+            endpoint: config.endpoint.to_string(),
+            passphrase,
+        })
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// `Ok(None)` if nothing was saved for this profile and endpoint.
+    pub fn load(&self) -> Result<Option<Session>, SessionError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let file =
+            serde_json::from_str(&content).map_err(|err| SessionError::Format(err.to_string()))?;
+
+        let stored: StoredSession = match file {
+            SessionFile::Plain { session } => session,
+            SessionFile::Encrypted {
+                salt,
+                nonce,
+                ciphertext,
+            } => {
+                let passphrase = self
+                    .passphrase
+                    .as_ref()
+                    .ok_or(SessionError::PassphraseRequired)?;
+
+                let plaintext = decrypt(passphrase, &salt, &nonce, &ciphertext)?;
+                serde_json::from_slice(&plaintext)
+                    .map_err(|err| SessionError::Format(err.to_string()))?
+            }
+        };
+
+        if stored.endpoint != self.endpoint {
+            return Ok(None);
+        }
+
+        Ok(Some(Session {
+            username: stored.username,
+            sign_in_response: SignInResponse {
+                user_id: stored.user_id,
+                access_token: stored.access_token,
+                ..Default::default()
+            },
+        }))
+    }
+
+    pub fn save(&self, session: &Session) -> Result<(), SessionError> {
+        let stored = StoredSession {
+            endpoint: self.endpoint.clone(),
+            username: session.username.clone(),
+            user_id: session.sign_in_response.user_id.clone(),
+            access_token: session.sign_in_response.access_token.clone(),
+        };
+
+        let file = match &self.passphrase {
+            Some(passphrase) => {
+                let plaintext = serde_json::to_vec(&stored)
+                    .map_err(|err| SessionError::Format(err.to_string()))?;
+                encrypt(passphrase, &plaintext)?
+            }
+            None => SessionFile::Plain { session: stored },
+        };
+        let content =
+            serde_json::to_string(&file).map_err(|err| SessionError::Format(err.to_string()))?;
+
+        write_private(&self.path, content.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<(), SessionError> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Writes through a temporary file that only the current user can read, so the tokens
+/// are never readable by others, not even for a moment.
+///
+/// The mode only applies to a file that is created, so a temporary file left over by an
+/// earlier run is removed rather than reused.
+fn write_private(path: &PathBuf, content: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+
+    let dir = path.parent().unwrap_or(path);
+    let mut dir_builder = fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut dir_builder, 0o700);
+    dir_builder.create(dir)?;
+
+    let temp_path = path.with_extension("tmp");
+    match fs::remove_file(&temp_path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(&temp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+
+    fs::rename(temp_path, path)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], SessionError> {
+    let mut key = [0; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| SessionError::Format(err.to_string()))?;
+
+    Ok(key)
+}
+
+fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<SessionFile, SessionError> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let nonce: [u8; NONCE_LEN] = rand::random();
+
+    let key = derive_key(passphrase, &salt)?;
+    let ciphertext = XChaCha20Poly1305::new(&key.into())
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| SessionError::Decrypt)?;
+
+    Ok(SessionFile::Encrypted {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt(
+    passphrase: &str,
+    salt: &str,
+    nonce: &str,
+    ciphertext: &str,
+) -> Result<Vec<u8>, SessionError> {
+    let decode = |value: &str| {
+        STANDARD
+            .decode(value)
+            .map_err(|err| SessionError::Format(err.to_string()))
+    };
+    let (salt, nonce, ciphertext) = (decode(salt)?, decode(nonce)?, decode(ciphertext)?);
+
+    if nonce.len() != NONCE_LEN {
+        return Err(SessionError::Format("invalid nonce".to_string()));
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    XChaCha20Poly1305::new(&key.into())
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| SessionError::Decrypt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn replaces_a_stale_temp_file_privately() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("session-test-{}", std::process::id()));
+        let path = dir.join("session.json");
+        fs::create_dir_all(&dir).unwrap();
+
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, "stale").unwrap();
+        fs::set_permissions(&temp_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, b"fresh").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fresh");
+        assert!(!temp_path.exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn encrypted_round_trip() {
+        let file = encrypt("correct horse", b"{\"token\":1}").unwrap();
+        let SessionFile::Encrypted {
+            salt,
+            nonce,
+            ciphertext,
+        } = file
+        else {
+            panic!("expected an encrypted file");
+        };
+
+        assert_eq!(
+            decrypt("correct horse", &salt, &nonce, &ciphertext).unwrap(),
+            b"{\"token\":1}"
+        );
+        assert!(matches!(
+            decrypt("wrong horse", &salt, &nonce, &ciphertext),
+            Err(SessionError::Decrypt)
+        ));
+    }
+}
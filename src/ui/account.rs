@@ -0,0 +1,287 @@
+use std::{
+    cell::RefCell,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use ratatui::{
+    prelude::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Text,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::{
+    app_event::{AppEvent, TaskResult},
+    app_state::AppState,
+    rpc::error::ClientError,
+};
+
+use super::{validation, Scene, Ui};
+
+pub struct AccountUi<'a> {
+    app_state: Arc<Mutex<RefCell<AppState>>>,
+    input_current_password: TextArea<'a>,
+    input_new_password: TextArea<'a>,
+    input_new_password_repeat: TextArea<'a>,
+    error_message: Option<String>,
+    is_pending: bool,
+    current_focus: Focus,
+}
+
+enum Focus {
+    CurrentPassword,
+    NewPassword,
+    NewPasswordRepeat,
+}
+
+fn password_input<'a>(title: &'static str, placeholder: &str) -> TextArea<'a> {
+    let mut input = TextArea::default();
+    input.set_block(Block::default().borders(Borders::ALL).title(title));
+    input.set_mask_char('\u{2022}'); //U+2022 BULLET (•)
+    input.set_placeholder_text(placeholder);
+    input.set_cursor_style(Style::default());
+
+    input
+}
+
+fn current_password_input<'a>() -> TextArea<'a> {
+    password_input("current password", "Please enter your current password")
+}
+
+impl<'a> AccountUi<'a> {
+    pub fn new(app_state: Arc<Mutex<RefCell<AppState>>>) -> Self {
+        let mut me = Self {
+            app_state,
+            input_current_password: TextArea::default(),
+            input_new_password: TextArea::default(),
+            input_new_password_repeat: TextArea::default(),
+            error_message: None,
+            is_pending: false,
+            current_focus: Focus::CurrentPassword,
+        };
+        me.clear();
+
+        me
+    }
+
+    fn focus(&mut self, focus: Focus) {
+        let enable_style = Style::default().bg(Color::White);
+        let disable_style = Style::default();
+
+        self.input_current_password.set_cursor_style(disable_style);
+        self.input_new_password.set_cursor_style(disable_style);
+        self.input_new_password_repeat
+            .set_cursor_style(disable_style);
+
+        match focus {
+            Focus::CurrentPassword => self.input_current_password.set_cursor_style(enable_style),
+            Focus::NewPassword => self.input_new_password.set_cursor_style(enable_style),
+            Focus::NewPasswordRepeat => self
+                .input_new_password_repeat
+                .set_cursor_style(enable_style),
+        }
+
+        self.current_focus = focus;
+    }
+
+    fn clear(&mut self) {
+        self.input_current_password = current_password_input();
+        self.input_new_password = password_input("new password", "Please enter a new password");
+        self.input_new_password_repeat = password_input(
+            "new password repeat",
+            "Please enter the new password again.",
+        );
+        self.error_message = None;
+        self.focus(Focus::CurrentPassword);
+    }
+
+    /// Validates the form and changes the password in the background; the result arrives as
+    /// `TaskResult::PasswordUpdated`.
+    fn submit(&mut self) -> Result<(), ClientError> {
+        let current_password = self
+            .input_current_password
+            .lines()
+            .join("")
+            .trim()
+            .to_string();
+        let new_password = self.input_new_password.lines().join("").trim().to_string();
+        let new_password_repeat = self
+            .input_new_password_repeat
+            .lines()
+            .join("")
+            .trim()
+            .to_string();
+
+        if current_password.is_empty() || new_password.is_empty() {
+            return Err(ClientError::Validation(
+                "Please enter your current and new password.".to_string(),
+            ));
+        }
+        validation::password(&new_password)?;
+        if new_password != new_password_repeat {
+            return Err(ClientError::Validation(
+                "The new passwords do not match.".to_string(),
+            ));
+        }
+        if new_password == current_password {
+            return Err(ClientError::Validation(
+                "The new password must differ from the current one.".to_string(),
+            ));
+        }
+
+        let (rpc, events) = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            if app_state.user.is_none() {
+                return Err(ClientError::NotSignedIn);
+            }
+
+            (app_state.rpc.clone(), app_state.events.clone())
+        };
+
+        events.spawn(async move {
+            let result = rpc
+                .account()
+                .update_password(current_password, new_password)
+                .await;
+
+            TaskResult::PasswordUpdated(result)
+        });
+
+        self.is_pending = true;
+
+        Ok(())
+    }
+
+    /// Tokens issued for the old password are not trusted any more, so the user signs in
+    /// again with the new one.
+    fn sign_out(&mut self) {
+        let mut app_state = self.app_state.lock().unwrap();
+        app_state.get_mut().sign_out(Some(
+            "Password changed. Please sign in with your new password.".to_string(),
+        ));
+    }
+}
+
+impl<'a> Ui for AccountUi<'a> {
+    fn ui(&self, f: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Min(3), // current password
+                Constraint::Min(3), // new password
+                Constraint::Min(3), // new password repeat
+                Constraint::Min(3),
+            ])
+            .split(f.size());
+
+        let title_block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default());
+
+        let title = Paragraph::new(Text::styled(
+            "Change password",
+            Style::default().fg(Color::Green),
+        ))
+        .block(title_block);
+
+        let err_message = match &self.error_message {
+            Some(err_message) => err_message.clone(),
+            None if self.is_pending => "Saving...".to_string(),
+            None => String::new(),
+        };
+
+        f.render_widget(title, layout[0]);
+        f.render_widget(self.input_current_password.widget(), layout[1]);
+        f.render_widget(self.input_new_password.widget(), layout[2]);
+        f.render_widget(self.input_new_password_repeat.widget(), layout[3]);
+        f.render_widget(
+            Paragraph::new(Text::styled(
+                err_message,
+                Style::default().fg(Color::LightRed),
+            )),
+            layout[4],
+        );
+    }
+
+    fn event_handle<'me>(
+        &'me mut self,
+        event: std::io::Result<crossterm::event::Event>,
+    ) -> Pin<Box<dyn std::future::Future<Output = io::Result<Scene>> + Send + 'me>> {
+        let me: &'me mut Self = self;
+
+        Box::pin(async {
+            match event?.into() {
+                Input { key: Key::Esc, .. } if !me.is_pending => {
+                    me.clear();
+                    return Ok(Scene::AfterSignIn);
+                }
+                Input { key: Key::Tab, .. } => {
+                    let focus = match me.current_focus {
+                        Focus::CurrentPassword => Focus::NewPassword,
+                        Focus::NewPassword => Focus::NewPasswordRepeat,
+                        Focus::NewPasswordRepeat => Focus::CurrentPassword,
+                    };
+
+                    me.focus(focus);
+                }
+                Input {
+                    key: Key::Enter, ..
+                } if !me.is_pending => match me.submit() {
+                    Ok(_) => me.error_message = None,
+                    Err(ClientError::NotSignedIn) => return Ok(Scene::SignIn),
+                    Err(err) => me.error_message = Some(err.to_string()),
+                },
+                Input {
+                    key: Key::Enter, ..
+                }
+                | Input { key: Key::Esc, .. } => {}
+                input => {
+                    match me.current_focus {
+                        Focus::CurrentPassword => me.input_current_password.input(input),
+                        Focus::NewPassword => me.input_new_password.input(input),
+                        Focus::NewPasswordRepeat => me.input_new_password_repeat.input(input),
+                    };
+                }
+            };
+
+            Ok(Scene::Account)
+        })
+    }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::PasswordUpdated(result)) => result,
+            _ => return None,
+        };
+
+        self.is_pending = false;
+
+        match result {
+            Ok(()) => {
+                self.clear();
+                self.sign_out();
+
+                Some(Scene::SignIn)
+            }
+            Err(ClientError::Unauthenticated(_)) => {
+                self.error_message = Some("The current password is incorrect.".to_string());
+                self.input_current_password = current_password_input();
+                self.focus(Focus::CurrentPassword);
+
+                None
+            }
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+
+                None
+            }
+        }
+    }
+}
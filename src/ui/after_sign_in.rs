@@ -30,14 +30,15 @@ impl<'a> AfterSignInUi<'a> {
             list_items: vec![
                 ListItem::new(Line::from(Span::styled("Chat", Style::default()))),
                 ListItem::new(Line::from(Span::styled("Profile", Style::default()))),
+                ListItem::new(Line::from(Span::styled("Account", Style::default()))),
+                ListItem::new(Line::from(Span::styled("Delete account", Style::default()))),
                 ListItem::new(Line::from(Span::styled("Sign out", Style::default()))),
             ],
         }
     }
 
     fn sign_out(&mut self) {
-        let mut app_state = self.app_state.lock().unwrap();
-        app_state.get_mut().user = None;
+        self.app_state.lock().unwrap().get_mut().sign_out(None);
     }
 }
 
@@ -112,9 +113,11 @@ impl<'a> Ui for AfterSignInUi<'a> {
                             }
                         }
                         KeyCode::Enter => match me.selected_index {
-                            // 0 => return Ok(Scene::SignIn),
+                            0 => return Ok(Scene::Chat),
                             1 => return Ok(Scene::Profile),
-                            2 => {
+                            2 => return Ok(Scene::Account),
+                            3 => return Ok(Scene::DeleteAccount),
+                            4 => {
                                 me.sign_out();
                                 return Ok(Scene::Main);
                             }
@@ -0,0 +1,225 @@
+use crate::rpc::{
+    client::RpcClient,
+    error::ClientError,
+    name::CategoryName,
+    server::ServerId,
+    ycchat::v1::models::{Category, Channel, Server},
+};
+
+use super::{next_page, PAGE_SIZE};
+
+/// Channels of a server in the order they are listed: channels outside any category
+/// first, then the channels of every category below its heading.
+#[derive(Default)]
+pub struct ChannelTree {
+    pub channels: Vec<Channel>,
+    /// category name shown above `channels[index]`; empty categories sit at the end
+    pub headings: Vec<(usize, String)>,
+}
+
+/// A line of the channel pane.
+pub enum Row<'t> {
+    Heading(&'t str),
+    /// index into `ChannelTree::channels`
+    Channel(usize),
+}
+
+impl ChannelTree {
+    pub fn new(categories: Vec<Category>, channels: Vec<Channel>) -> Self {
+        let mut uncategorized = Vec::new();
+        let mut categorized: Vec<Vec<Channel>> = vec![Vec::new(); categories.len()];
+
+        for channel in channels {
+            let category = channel
+                .parent
+                .parse::<CategoryName>()
+                .ok()
+                .and_then(|parent| {
+                    categories.iter().position(|category| {
+                        category
+                            .name
+                            .parse::<CategoryName>()
+                            .is_ok_and(|name| name == parent)
+                    })
+                });
+
+            match category {
+                Some(index) => categorized[index].push(channel),
+                None => uncategorized.push(channel),
+            }
+        }
+
+        let mut channels = uncategorized;
+        let mut headings = Vec::with_capacity(categories.len());
+        for (category, category_channels) in categories.into_iter().zip(categorized) {
+            headings.push((channels.len(), category.display_name));
+            channels.extend(category_channels);
+        }
+
+        Self { channels, headings }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Channel> {
+        self.channels.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    pub fn rows(&self) -> Vec<Row<'_>> {
+        let mut rows = Vec::with_capacity(self.channels.len() + self.headings.len());
+        let mut headings = self.headings.iter().peekable();
+
+        for index in 0..=self.channels.len() {
+            while let Some((_, heading)) = headings.next_if(|(at, _)| *at == index) {
+                rows.push(Row::Heading(heading));
+            }
+            if index < self.channels.len() {
+                rows.push(Row::Channel(index));
+            }
+        }
+
+        rows
+    }
+}
+
+/// Pages through every server the user is a member of.
+pub async fn fetch_servers(rpc: RpcClient) -> Result<Vec<Server>, ClientError> {
+    let mut server_service = rpc.server();
+    let mut servers = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let response = server_service.list_server(PAGE_SIZE, page_token).await?;
+
+        servers.extend(response.servers);
+
+        match next_page(response.next_page_token) {
+            Some(next_page_token) => page_token = Some(next_page_token),
+            None => break,
+        }
+    }
+
+    Ok(servers)
+}
+
+/// Pages through every category and channel of the server.
+pub async fn fetch_channel_tree(
+    rpc: RpcClient,
+    server_id: ServerId,
+) -> Result<ChannelTree, ClientError> {
+    let categories = async {
+        let mut category_service = rpc.category();
+        let mut categories = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let response = category_service
+                .list_categories(server_id, PAGE_SIZE, page_token)
+                .await?;
+
+            categories.extend(response.categories);
+
+            match next_page(response.next_page_token) {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => break,
+            }
+        }
+
+        Ok::<_, ClientError>(categories)
+    };
+
+    let channels = async {
+        let mut channel_service = rpc.channel();
+        let mut channels = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let response = channel_service
+                .list_server_channels(server_id, PAGE_SIZE, page_token)
+                .await?;
+
+            channels.extend(response.channels);
+
+            match next_page(response.next_page_token) {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => break,
+            }
+        }
+
+        Ok::<_, ClientError>(channels)
+    };
+
+    let (categories, channels) = tokio::try_join!(categories, channels)?;
+
+    Ok(ChannelTree::new(categories, channels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER: &str = "servers/01HK3F6GZ3C8Y2Q9W7V6T5R4E3";
+
+    fn category(id: &str, display_name: &str) -> Category {
+        Category {
+            name: format!("{}/categories/{}", SERVER, id),
+            display_name: display_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn channel(display_name: &str, parent: String) -> Channel {
+        Channel {
+            display_name: display_name.to_string(),
+            parent,
+            ..Default::default()
+        }
+    }
+
+    fn labels(tree: &ChannelTree) -> Vec<String> {
+        tree.rows()
+            .into_iter()
+            .map(|row| match row {
+                Row::Heading(heading) => format!("[{}]", heading),
+                Row::Channel(index) => tree.channels[index].display_name.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_channels_under_categories() {
+        let text = "01HK3F6GZ3C8Y2Q9W7V6T5R4E4";
+        let voice = "01HK3F6GZ3C8Y2Q9W7V6T5R4E5";
+        let empty = "01HK3F6GZ3C8Y2Q9W7V6T5R4E6";
+        let in_category = |id: &str| format!("{}/categories/{}", SERVER, id);
+
+        let tree = ChannelTree::new(
+            vec![
+                category(text, "Text"),
+                category(voice, "Voice"),
+                category(empty, "Empty"),
+            ],
+            vec![
+                channel("general", in_category(text)),
+                channel("lounge", in_category(voice)),
+                channel("announcements", SERVER.to_string()),
+                channel("random", in_category(text)),
+            ],
+        );
+
+        assert_eq!(
+            labels(&tree),
+            [
+                "announcements",
+                "[Text]",
+                "general",
+                "random",
+                "[Voice]",
+                "lounge",
+                "[Empty]"
+            ]
+        );
+    }
+}
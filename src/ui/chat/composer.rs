@@ -0,0 +1,216 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    prelude::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use tui_textarea::TextArea;
+
+use crate::rpc::message::MessageId;
+
+const MAX_VISIBLE_LINES: u16 = 6;
+
+/// Multi-line message input: Enter sends, Shift+Enter or Alt+Enter starts a new line.
+pub struct Composer<'a> {
+    textarea: TextArea<'a>,
+    mode: Mode,
+}
+
+enum Mode {
+    New,
+    /// replying to a message, with a one-line preview of it
+    Reply {
+        message_id: MessageId,
+        preview: String,
+    },
+    Edit {
+        message_id: MessageId,
+    },
+}
+
+pub enum Draft {
+    New {
+        content: String,
+        reply_to: Option<MessageId>,
+    },
+    Edit {
+        message_id: MessageId,
+        content: String,
+    },
+}
+
+impl<'a> Composer<'a> {
+    pub fn new() -> Self {
+        let mut textarea = TextArea::default();
+        textarea.set_block(Block::default().borders(Borders::ALL));
+        textarea.set_cursor_style(Style::default());
+        textarea.set_cursor_line_style(Style::default());
+
+        Self {
+            textarea,
+            mode: Mode::New,
+        }
+    }
+
+    pub fn set_focus(&mut self, is_focused: bool) {
+        let cursor_style = if is_focused {
+            Style::default().bg(Color::White)
+        } else {
+            Style::default()
+        };
+
+        self.textarea.set_cursor_style(cursor_style);
+    }
+
+    pub fn set_placeholder(&mut self, placeholder: String) {
+        self.textarea.set_placeholder_text(placeholder);
+    }
+
+    pub fn set_reply(&mut self, message_id: MessageId, preview: String) {
+        self.cancel();
+        self.mode = Mode::Reply {
+            message_id,
+            preview,
+        };
+    }
+
+    /// Replaces the input with `content`; submitting it edits the message.
+    pub fn start_edit(&mut self, message_id: MessageId, content: &str) {
+        self.cancel();
+        self.clear();
+        self.textarea.insert_str(content);
+        self.mode = Mode::Edit { message_id };
+    }
+
+    /// Whether the draft replies to or edits a message.
+    pub fn has_target(&self) -> bool {
+        !matches!(self.mode, Mode::New)
+    }
+
+    /// Drops the reply target, or the edit together with its text.
+    pub fn cancel(&mut self) {
+        if let Mode::Edit { .. } = std::mem::replace(&mut self.mode, Mode::New) {
+            self.clear();
+        }
+    }
+
+    /// Returns the draft when the key submits the message.
+    pub fn input(&mut self, key: KeyEvent) -> Option<Draft> {
+        if key.code != KeyCode::Enter {
+            self.textarea.input(key);
+            return None;
+        }
+
+        if key
+            .modifiers
+            .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT)
+        {
+            self.textarea.insert_newline();
+            return None;
+        }
+
+        self.take()
+    }
+
+    /// The word being typed after an `@`, e.g. `ali` for `hi @ali`.
+    pub fn mention_query(&self) -> Option<String> {
+        let (row, col) = self.textarea.cursor();
+        let before_cursor: String = self.textarea.lines()[row].chars().take(col).collect();
+
+        let word = before_cursor
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default();
+
+        word.strip_prefix('@').map(str::to_string)
+    }
+
+    /// Replaces the `@query` before the cursor with a mention token.
+    pub fn complete_mention(&mut self, token: &str) {
+        let query_len = match self.mention_query() {
+            Some(query) => query.chars().count(),
+            None => return,
+        };
+
+        // the query and its `@`
+        for _ in 0..=query_len {
+            self.textarea.delete_char();
+        }
+        self.textarea.insert_str(format!("{} ", token));
+    }
+
+    /// Clears the input and returns its content, unless it is blank.
+    fn take(&mut self) -> Option<Draft> {
+        let content = self.textarea.lines().join("\n").trim().to_string();
+        if content.is_empty() {
+            return None;
+        }
+
+        self.clear();
+
+        let draft = match std::mem::replace(&mut self.mode, Mode::New) {
+            Mode::New => Draft::New {
+                content,
+                reply_to: None,
+            },
+            Mode::Reply { message_id, .. } => Draft::New {
+                content,
+                reply_to: Some(message_id),
+            },
+            Mode::Edit { message_id } => Draft::Edit {
+                message_id,
+                content,
+            },
+        };
+
+        Some(draft)
+    }
+
+    fn clear(&mut self) {
+        self.textarea.select_all();
+        self.textarea.cut();
+    }
+
+    /// Rows needed to show the input, borders and reply/edit banner included.
+    pub fn height(&self) -> u16 {
+        let banner_height = match self.mode {
+            Mode::New => 0,
+            _ => 1,
+        };
+
+        (self.textarea.lines().len() as u16).min(MAX_VISIBLE_LINES) + 2 + banner_height
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let hint_style = Style::default().fg(Color::DarkGray);
+
+        let banner = match &self.mode {
+            Mode::New => {
+                f.render_widget(self.textarea.widget(), area);
+                return;
+            }
+            Mode::Reply { preview, .. } => Line::from(vec![
+                Span::styled("\u{21aa} replying to ", hint_style),
+                Span::styled(preview.clone(), hint_style.add_modifier(Modifier::ITALIC)),
+                Span::styled("  (Esc: cancel)", hint_style),
+            ]),
+            Mode::Edit { .. } => Line::from(vec![
+                Span::styled(
+                    "\u{270e} editing message",
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled("  (Esc: cancel)", hint_style),
+            ]),
+        };
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(3)])
+            .split(area);
+
+        f.render_widget(Paragraph::new(banner), layout[0]);
+        f.render_widget(self.textarea.widget(), layout[1]);
+    }
+}
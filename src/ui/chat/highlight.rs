@@ -0,0 +1,156 @@
+use std::sync::OnceLock;
+
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+const THEME: &str = "base16-ocean.dark";
+// an untagged block needs this many statement-shaped lines to be guessed
+const MIN_STATEMENT_LINES: usize = 2;
+
+/// Statement openers of the languages guessed from the content, by file extension.
+const GUESSES: &[(&str, &[&str])] = &[
+    (
+        "rs",
+        &[
+            "fn ", "pub ", "let ", "use ", "impl ", "struct ", "enum ", "mod ", "match ", "#[",
+        ],
+    ),
+    (
+        "sql",
+        &[
+            "SELECT ",
+            "FROM ",
+            "WHERE ",
+            "JOIN ",
+            "GROUP BY ",
+            "ORDER BY ",
+            "INSERT INTO ",
+            "VALUES ",
+            "UPDATE ",
+            "SET ",
+            "DELETE FROM ",
+            "CREATE TABLE ",
+        ],
+    ),
+];
+
+/// Grammars and themes bundled with syntect, loaded on first use.
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    &THEME_SET.get_or_init(ThemeSet::load_defaults).themes[THEME]
+}
+
+/// The fence tag (`rust`, `sql`, `sh`, ...) wins; otherwise the code is recognized by its
+/// first line (shebangs, `<?php`, ...). Fenced blocks without a known tag are also guessed
+/// from their content.
+fn find_syntax(language: Option<&str>, code: &str) -> Option<&'static SyntaxReference> {
+    let syntax_set = syntax_set();
+    let token = language
+        .and_then(|language| language.split_whitespace().next())
+        .unwrap_or_default();
+
+    if !token.is_empty() {
+        if let Some(syntax) = syntax_set.find_syntax_by_token(token) {
+            return Some(syntax);
+        }
+    }
+
+    if let Some(syntax) = syntax_set.find_syntax_by_first_line(code.lines().next()?) {
+        return Some(syntax);
+    }
+
+    // indented blocks are often just indented prose
+    language?;
+    syntax_set.find_syntax_by_extension(guess_extension(code)?)
+}
+
+/// A language whose statements make up at least half of the non-blank lines, and at least
+/// `MIN_STATEMENT_LINES` of them.
+fn guess_extension(code: &str) -> Option<&'static str> {
+    let lines: Vec<&str> = code
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    GUESSES.iter().find_map(|(extension, openers)| {
+        let statements = lines
+            .iter()
+            .filter(|line| is_statement(line, openers))
+            .count();
+
+        (statements >= MIN_STATEMENT_LINES && statements * 2 >= lines.len()).then_some(*extension)
+    })
+}
+
+/// Starts like a statement and ends like one, not like a sentence.
+fn is_statement(line: &str, openers: &[&str]) -> bool {
+    let starts = openers.iter().any(|opener| {
+        line.get(..opener.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(opener))
+    });
+    let ends = !line.ends_with(['.', '!', '?', ':']);
+
+    starts && ends
+}
+
+/// One list of spans per line of `code`, or `None` if the language is unknown.
+/// `language` is the fence tag, `None` for an indented block.
+pub fn highlight(language: Option<&str>, code: &str) -> Option<Vec<Vec<Span<'static>>>> {
+    let syntax = find_syntax(language, code)?;
+    let mut highlighter = HighlightLines::new(syntax, theme());
+
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(code) {
+        let ranges = highlighter.highlight_line(line, syntax_set()).ok()?;
+
+        let spans = ranges
+            .into_iter()
+            .map(|(style, text)| {
+                let color = style.foreground;
+                Span::styled(
+                    text.trim_end_matches('\n').to_string(),
+                    Style::default().fg(Color::Rgb(color.r, color.g, color.b)),
+                )
+            })
+            .collect();
+
+        lines.push(spans);
+    }
+
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_untagged_code() {
+        assert!(highlight(Some("rust"), "let a = 1;").is_some());
+        assert!(highlight(None, "#!/bin/sh\necho hi").is_some());
+        assert!(highlight(Some(""), "SELECT id, name\nFROM users\nWHERE id = 1;").is_some());
+        assert!(highlight(Some(""), "use std::io;\n\nfn main() {\n    let a = 1;\n}").is_some());
+    }
+
+    #[test]
+    fn prose_stays_plain() {
+        assert!(highlight(Some(""), "Let me check the logs.\nSelect the failing run.").is_none());
+        assert!(highlight(Some(""), "update: deployed\nfrom now on use main").is_none());
+        // indented blocks are never guessed
+        assert!(highlight(None, "SELECT id\nFROM users;").is_none());
+    }
+}
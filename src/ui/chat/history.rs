@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+
+use ulid::Ulid;
+
+use crate::rpc::{
+    channel::ChannelId,
+    client::RpcClient,
+    error::ClientError,
+    message::MessageId,
+    name::MessageName,
+    ycchat::v1::{
+        models::{Message, Reaction},
+        services::message::ListMessagesResponse,
+    },
+};
+
+/// Messages loaded for one channel, ordered by their ULID (i.e. by creation time),
+/// followed by the messages this client is still sending.
+#[derive(Default)]
+pub struct History {
+    pub messages: BTreeMap<MessageId, Message>,
+    pub outgoing: Vec<Outgoing>,
+    /// token for the next page of older messages; `None` once the beginning was reached
+    pub next_page_token: Option<String>,
+    pub is_loading: bool,
+    /// reactions of loaded messages, in the order the server listed them
+    pub reactions: HashMap<MessageId, Vec<Reaction>>,
+    /// newest message acknowledged to the server
+    pub acknowledged: Option<MessageId>,
+}
+
+pub struct Outgoing {
+    /// client-side id, only used to match the `speech` result
+    pub local_id: Ulid,
+    pub content: String,
+    pub reply_to: Option<MessageId>,
+    pub state: SendState,
+}
+
+pub enum SendState {
+    Pending,
+    Failed(String),
+}
+
+impl History {
+    pub fn last_seen(&self) -> Option<MessageId> {
+        self.messages.keys().next_back().copied()
+    }
+
+    /// Returns the message with the position it had.
+    pub fn remove(&mut self, message_id: MessageId) -> Option<(usize, Message)> {
+        let position = self.messages.range(..message_id).count();
+
+        self.messages
+            .remove(&message_id)
+            .map(|message| (position, message))
+    }
+
+    pub fn get(&self, message_id: MessageId) -> Option<&Message> {
+        self.messages.get(&message_id)
+    }
+
+    pub fn reactions_of(&self, message: &Message) -> &[Reaction] {
+        message
+            .name
+            .parse::<MessageName>()
+            .ok()
+            .and_then(|name| self.reactions.get(&name.message_id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// `index` counts loaded messages first, then outgoing ones.
+    pub fn message_at(&self, index: usize) -> Option<&Message> {
+        self.messages.values().nth(index)
+    }
+
+    /// Loaded messages that reply to `message_id`, oldest first.
+    pub fn replies_to(&self, message_id: MessageId) -> impl Iterator<Item = &Message> {
+        self.messages
+            .values()
+            .filter(move |message| reply_to(message) == Some(message_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len() + self.outgoing.len()
+    }
+
+    /// Adds or replaces a message; returns its position if it was not loaded before.
+    pub fn insert(&mut self, message: Message) -> Option<usize> {
+        let message_id = message.name.parse::<MessageName>().ok()?.message_id;
+
+        match self.messages.insert(message_id, message) {
+            Some(_) => None,
+            None => Some(self.messages.range(..message_id).count()),
+        }
+    }
+
+    /// `index` counts loaded messages first, then outgoing ones.
+    pub fn outgoing_at(&mut self, index: usize) -> Option<&mut Outgoing> {
+        let index = index.checked_sub(self.messages.len())?;
+
+        self.outgoing.get_mut(index)
+    }
+
+    pub fn take_outgoing(&mut self, local_id: Ulid) -> Option<Outgoing> {
+        let index = self
+            .outgoing
+            .iter()
+            .position(|outgoing| outgoing.local_id == local_id)?;
+
+        Some(self.outgoing.remove(index))
+    }
+}
+
+/// The message `message` replies to, if any.
+pub fn reply_to(message: &Message) -> Option<MessageId> {
+    message
+        .reply_to
+        .parse::<MessageName>()
+        .ok()
+        .map(|name| name.message_id)
+}
+
+/// Pages back from the newest message until `last_seen` (or the beginning of the channel)
+/// is reached. Pages are newest first, so everything newer than `last_seen` is covered.
+pub async fn fetch_since(
+    rpc: RpcClient,
+    channel_id: ChannelId,
+    last_seen: MessageId,
+    page_size: i32,
+) -> Result<Vec<Message>, ClientError> {
+    page_back_to(last_seen, |page_token| {
+        let rpc = rpc.clone();
+        async move {
+            rpc.message()
+                .list_messages(channel_id, page_size, page_token)
+                .await
+        }
+    })
+    .await
+}
+
+// No page limit: stopping early would merge both ends of the gap as if they were
+// contiguous and silently drop what lies between.
+async fn page_back_to<F, Fut>(
+    last_seen: MessageId,
+    mut list_page: F,
+) -> Result<Vec<Message>, ClientError>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<ListMessagesResponse, ClientError>>,
+{
+    let mut messages = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let response = list_page(page_token).await?;
+
+        let reached_last_seen = response.messages.iter().any(|message| {
+            message
+                .name
+                .parse::<MessageName>()
+                .is_ok_and(|name| name.message_id <= last_seen)
+        });
+
+        messages.extend(response.messages);
+
+        match response.next_page_token {
+            Some(next_page_token) if !reached_last_seen && !next_page_token.is_empty() => {
+                page_token = Some(next_page_token)
+            }
+            _ => break,
+        }
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: u128) -> Message {
+        Message {
+            name: MessageName::new(Ulid(id)).to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// One message per page, newest first: message `pages` down to 1.
+    async fn backfill(pages: u128, last_seen: u128) -> Vec<Message> {
+        page_back_to(Ulid(last_seen), |page_token| {
+            let id = page_token.map_or(pages, |token| token.parse().unwrap());
+            let next_page_token = (id > 1).then(|| (id - 1).to_string());
+
+            async move {
+                Ok(ListMessagesResponse {
+                    messages: vec![message(id)],
+                    next_page_token,
+                })
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn backfill_pages_until_last_seen() {
+        let messages = backfill(40, 3).await;
+
+        assert_eq!(messages.len(), 38);
+        assert_eq!(messages.last(), Some(&message(3)));
+    }
+
+    #[tokio::test]
+    async fn backfill_stops_at_the_beginning() {
+        let messages = backfill(25, 0).await;
+
+        assert_eq!(messages.len(), 25);
+        assert_eq!(messages.last(), Some(&message(1)));
+    }
+}
@@ -0,0 +1,384 @@
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::highlight;
+use super::mention::{self, MemberNames};
+
+/// Renders the chat subset of Markdown: emphasis, strikethrough, inline code, fenced code
+/// blocks, quotes, lists and links. Line breaks in the source are kept, as users expect in chat.
+pub fn render(
+    content: &str,
+    style: Style,
+    names: &MemberNames,
+    my_name: Option<&str>,
+) -> Vec<Line<'static>> {
+    let mut renderer = Renderer {
+        names,
+        my_name,
+        lines: Vec::new(),
+        spans: Vec::new(),
+        styles: vec![style],
+        text: String::new(),
+        quote_depth: 0,
+        lists: Vec::new(),
+        bullet: None,
+        code_block: None,
+        link: None,
+    };
+
+    for event in Parser::new_ext(content, Options::ENABLE_STRIKETHROUGH) {
+        renderer.event(event);
+    }
+
+    renderer.finish()
+}
+
+/// Content of every fenced or indented code block, in order.
+pub fn code_blocks(content: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+
+    for event in Parser::new_ext(content, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => current = Some(String::new()),
+            Event::Text(text) => {
+                if let Some(code) = &mut current {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => blocks.extend(current.take()),
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+struct Renderer<'n> {
+    names: &'n MemberNames,
+    my_name: Option<&'n str>,
+    lines: Vec<Line<'static>>,
+    /// spans of the line being built
+    spans: Vec<Span<'static>>,
+    /// inline styles, innermost last; the first one is the message style
+    styles: Vec<Style>,
+    /// text not turned into spans yet, so mentions split across text events stay whole
+    text: String,
+    quote_depth: usize,
+    /// next number of every open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// marker for the first line of the current list item
+    bullet: Option<String>,
+    /// text of the open code block
+    code_block: Option<String>,
+    /// target and text of the open link
+    link: Option<(String, String)>,
+}
+
+impl<'n> Renderer<'n> {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code_block {
+                Some(code) => code.push_str(&text),
+                None => self.push_text(&text),
+            },
+            Event::Code(code) => {
+                let style = self.style().fg(Color::Yellow);
+                self.push_span(Span::styled(code.to_string(), style));
+            }
+            Event::Html(html) => self.push_text(&html),
+            Event::SoftBreak | Event::HardBreak => self.end_line(),
+            Event::Rule => {
+                self.end_line();
+                self.push_span(Span::styled(
+                    "\u{2500}".repeat(8),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                self.end_line();
+            }
+            Event::FootnoteReference(_) | Event::TaskListMarker(_) => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph | Tag::Heading(..) => {
+                self.end_line();
+                if matches!(tag, Tag::Heading(..)) {
+                    self.push_style(Style::default().add_modifier(Modifier::BOLD));
+                }
+            }
+            Tag::BlockQuote => {
+                self.end_line();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.end_line();
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.end_line();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.end_line();
+                self.bullet = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        Some(format!("{}. ", *number - 1))
+                    }
+                    _ => Some("\u{2022} ".to_string()),
+                };
+            }
+            Tag::Emphasis => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => {
+                self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT))
+            }
+            Tag::Link(_, url, _) => {
+                self.push_style(
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::UNDERLINED),
+                );
+                self.link = Some((url.to_string(), String::new()));
+            }
+            Tag::FootnoteDefinition(_)
+            | Tag::Table(_)
+            | Tag::TableHead
+            | Tag::TableRow
+            | Tag::TableCell
+            | Tag::Image(..) => {}
+        }
+    }
+
+    fn end(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.end_line(),
+            Tag::Heading(..) => {
+                self.pop_style();
+                self.end_line();
+            }
+            Tag::BlockQuote => {
+                self.end_line();
+                self.quote_depth -= 1;
+            }
+            Tag::CodeBlock(kind) => {
+                let language = match &kind {
+                    CodeBlockKind::Fenced(language) => Some(language.as_ref()),
+                    CodeBlockKind::Indented => None,
+                };
+
+                if let Some(code) = self.code_block.take() {
+                    self.push_code_block(language, &code);
+                }
+            }
+            Tag::List(_) => {
+                self.end_line();
+                self.lists.pop();
+            }
+            Tag::Item => self.end_line(),
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough => self.pop_style(),
+            Tag::Link(..) => {
+                self.pop_style();
+
+                // the target is shown unless it is the link text itself
+                if let Some((url, text)) = self.link.take() {
+                    if url != text && !url.is_empty() {
+                        self.push_span(Span::styled(
+                            format!(" ({})", url),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                }
+            }
+            Tag::FootnoteDefinition(_)
+            | Tag::Table(_)
+            | Tag::TableHead
+            | Tag::TableRow
+            | Tag::TableCell
+            | Tag::Image(..) => {}
+        }
+    }
+
+    /// `language` is the fence tag, `None` for an indented block.
+    fn push_code_block(&mut self, language: Option<&str>, code: &str) {
+        let lines = highlight::highlight(language, code).unwrap_or_else(|| {
+            // unknown language: plain code style
+            code.lines()
+                .map(|line| {
+                    vec![Span::styled(
+                        line.to_string(),
+                        Style::default().fg(Color::Yellow),
+                    )]
+                })
+                .collect()
+        });
+
+        for spans in lines {
+            self.push_span(Span::styled(
+                "\u{2502} ",
+                Style::default().fg(Color::DarkGray),
+            ));
+            for span in spans {
+                self.push_span(span);
+            }
+            self.end_line();
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.flush_text();
+        self.styles.push(self.style().patch(style));
+    }
+
+    fn pop_style(&mut self) {
+        self.flush_text();
+        // the message style always stays
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push_str(text);
+        }
+
+        self.text.push_str(text);
+    }
+
+    fn flush_text(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+
+        let text = std::mem::take(&mut self.text);
+        let spans = mention::spans(&text, self.style(), self.names, self.my_name);
+        for span in spans {
+            self.push_span(span);
+        }
+    }
+
+    fn push_span(&mut self, span: Span<'static>) {
+        self.flush_text();
+
+        if self.spans.is_empty() {
+            self.spans = self.line_prefix();
+        }
+        self.spans.push(span);
+    }
+
+    /// Quote bars and list indentation that every line of the current block starts with.
+    fn line_prefix(&mut self) -> Vec<Span<'static>> {
+        let mut prefix = Vec::new();
+        let prefix_style = Style::default().fg(Color::DarkGray);
+
+        for _ in 0..self.quote_depth {
+            prefix.push(Span::styled("\u{258e} ", prefix_style));
+        }
+
+        let depth = self.lists.len();
+        if depth > 0 {
+            let indent = "  ".repeat(depth - 1);
+            let marker = self.bullet.take().unwrap_or_else(|| "  ".to_string());
+            prefix.push(Span::styled(format!("{}{}", indent, marker), prefix_style));
+        }
+
+        prefix
+    }
+
+    fn end_line(&mut self) {
+        self.flush_text();
+
+        if !self.spans.is_empty() {
+            self.lines.push(Line::from(std::mem::take(&mut self.spans)));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.end_line();
+        self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(content: &str) -> Vec<String> {
+        render(content, Style::default(), &MemberNames::new(), None)
+            .into_iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_line_breaks() {
+        assert_eq!(texts("hello\nworld"), ["hello", "world"]);
+    }
+
+    #[test]
+    fn lists_and_quotes() {
+        assert_eq!(
+            texts("- one\n- two\n\n1. first\n2. second\n\n> quoted"),
+            [
+                "\u{2022} one",
+                "\u{2022} two",
+                "1. first",
+                "2. second",
+                "\u{258e} quoted"
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_and_links() {
+        assert_eq!(
+            texts("```rust\nlet a = 1;\n```\n[docs](https://example.com)"),
+            ["\u{2502} let a = 1;", "docs (https://example.com)"]
+        );
+    }
+
+    #[test]
+    fn extracts_code_blocks() {
+        assert_eq!(
+            code_blocks("run\n```sh\ncargo test\n```\nthen\n```\nSELECT 1;\n```"),
+            ["cargo test\n", "SELECT 1;\n"]
+        );
+    }
+
+    #[test]
+    fn mentions_stay_whole() {
+        let user_name = "users/01HK3F6GZ3C8Y2Q9W7V6T5R4E3";
+        let names = MemberNames::from([(user_name.to_string(), "alice".to_string())]);
+
+        let lines = render(
+            &format!("hi <@{}>!", user_name),
+            Style::default(),
+            &names,
+            None,
+        );
+        let text: String = lines[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+
+        assert_eq!(text, "hi @alice!");
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyCode;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::app_event::TaskResult;
+use crate::rpc::{
+    client::RpcClient,
+    error::ClientError,
+    name::{MemberName, UserName},
+    server::ServerId,
+    ycchat::v1::models::{Message, ServerMember},
+};
+
+use super::{next_page, ChatUi, Focus, PAGE_SIZE};
+
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Display names by user name (`users/{id}`).
+pub type MemberNames = HashMap<String, String>;
+
+/// Mentions are stored in the content as `<@users/{id}>`.
+pub fn token(user_name: &str) -> String {
+    format!("<@{}>", user_name)
+}
+
+pub fn is_mention(message: &Message, user_name: &str) -> bool {
+    message.content.contains(&token(user_name))
+}
+
+fn user_name(member: &ServerMember) -> Option<String> {
+    let name = member.name.parse::<MemberName>().ok()?;
+
+    Some(UserName::new(name.user_id).to_string())
+}
+
+/// Splits a line into text and mentions, showing mentions as `@display name`.
+pub fn spans(
+    line: &str,
+    style: Style,
+    names: &MemberNames,
+    my_name: Option<&str>,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find("<@") {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        let user_name = &rest[start + 2..end];
+        if user_name.parse::<UserName>().is_err() {
+            spans.push(Span::styled(rest[..end + 1].to_string(), style));
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), style));
+        }
+
+        let label = match names.get(user_name) {
+            Some(display_name) => format!("@{}", display_name),
+            None => format!("@{}", user_name),
+        };
+        let mention_style = if Some(user_name) == my_name {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            style.fg(Color::Cyan)
+        };
+        spans.push(Span::styled(label, mention_style));
+
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), style));
+    }
+
+    spans
+}
+
+/// Members whose display name matches `query`, best match first.
+fn suggestions<'m>(members: &'m [ServerMember], query: &str) -> Vec<&'m ServerMember> {
+    let matcher = SkimMatcherV2::default();
+
+    let mut scored: Vec<(i64, &ServerMember)> = members
+        .iter()
+        .filter_map(|member| {
+            let score = matcher.fuzzy_match(&member.display_name, query)?;
+            Some((score, member))
+        })
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, member)| member)
+        .collect()
+}
+
+/// Pages through every member of the server.
+async fn fetch_members(
+    rpc: RpcClient,
+    server_id: ServerId,
+) -> Result<Vec<ServerMember>, ClientError> {
+    let mut member_service = rpc.member();
+    let mut members = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let response = member_service
+            .list_server_members(server_id, PAGE_SIZE, page_token)
+            .await?;
+
+        members.extend(response.server_members);
+
+        match next_page(response.next_page_token) {
+            Some(next_page_token) => page_token = Some(next_page_token),
+            None => break,
+        }
+    }
+
+    Ok(members)
+}
+
+impl<'a> ChatUi<'a> {
+    pub(super) fn load_members(&self, server_id: ServerId) {
+        if self.members.contains_key(&server_id) {
+            return;
+        }
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = fetch_members(rpc, server_id).await;
+
+            TaskResult::Members(server_id, result)
+        });
+    }
+
+    /// Display names of the current server's members.
+    pub(super) fn member_names(&self) -> MemberNames {
+        self.server_id()
+            .and_then(|server_id| self.members.get(&server_id))
+            .map(|members| {
+                members
+                    .iter()
+                    .filter_map(|member| Some((user_name(member)?, member.display_name.clone())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Suggestions for the `@query` being typed; empty while the popup is closed.
+    fn mention_suggestions(&self) -> Vec<&ServerMember> {
+        if self.current_focus != Focus::Composer {
+            return Vec::new();
+        }
+
+        let query = match self.composer.mention_query() {
+            Some(query) if self.mention_dismissed.as_ref() != Some(&query) => query,
+            _ => return Vec::new(),
+        };
+
+        match self
+            .server_id()
+            .and_then(|server_id| self.members.get(&server_id))
+        {
+            Some(members) => suggestions(members, &query),
+            None => Vec::new(),
+        }
+    }
+
+    pub(super) fn is_mention_popup_open(&self) -> bool {
+        !self.mention_suggestions().is_empty()
+    }
+
+    pub(super) fn mention_key(&mut self, code: KeyCode) {
+        let count = self.mention_suggestions().len();
+
+        match code {
+            KeyCode::Up => self.mention_index = (self.mention_index + count - 1) % count,
+            KeyCode::Down => self.mention_index = (self.mention_index + 1) % count,
+            KeyCode::Enter | KeyCode::Tab => {
+                let token = self
+                    .mention_suggestions()
+                    .get(self.mention_index)
+                    .and_then(|member| user_name(member))
+                    .map(|user_name| token(&user_name));
+
+                if let Some(token) = token {
+                    self.composer.complete_mention(&token);
+                }
+                self.mention_index = 0;
+            }
+            KeyCode::Esc => self.mention_dismissed = self.composer.mention_query(),
+            _ => {}
+        }
+    }
+
+    /// Draws the suggestions right above the composer.
+    pub(super) fn render_mention_popup(&self, f: &mut Frame, composer_area: Rect) {
+        let suggestions = self.mention_suggestions();
+        if suggestions.is_empty() {
+            return;
+        }
+
+        let height = (suggestions.len() as u16 + 2).min(composer_area.y);
+        let area = Rect {
+            x: composer_area.x + 1,
+            y: composer_area.y - height,
+            width: composer_area.width.saturating_sub(2).min(40),
+            height,
+        };
+
+        let items: Vec<ListItem> = suggestions
+            .iter()
+            .map(|member| ListItem::new(format!("@{}", member.display_name)))
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("mention"))
+            .highlight_style(Style::default().bg(Color::LightCyan));
+
+        let mut state = ListState::default().with_selected(Some(self.mention_index));
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
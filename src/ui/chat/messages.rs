@@ -0,0 +1,413 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use prost_types::Timestamp;
+use ratatui::{
+    prelude::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::rpc::{message::MessageId, name::UserName, ycchat::v1::models::Message};
+
+use super::history::{self, History, Outgoing, SendState};
+use super::markdown;
+use super::mention::{self, MemberNames};
+use super::{reactions, ChatUi, Focus};
+
+const EXCERPT_LENGTH: usize = 60;
+
+/// Content lines of a message as last rendered, by message name.
+pub(super) type RenderedContents = HashMap<String, RenderedContent>;
+
+pub(super) struct RenderedContent {
+    update_time: Option<Timestamp>,
+    lines: Vec<Line<'static>>,
+}
+
+impl<'a> ChatUi<'a> {
+    pub(super) fn render_messages(&self, f: &mut Frame, area: Rect) {
+        let title = match self.channels.get(self.channel_index) {
+            Some(channel) if self.show_raw => format!("# {} (raw)", channel.display_name),
+            Some(channel) => format!("# {}", channel.display_name),
+            None => "messages".to_string(),
+        };
+        let block = self.pane_block(&title, Focus::Messages);
+
+        let history = match self.history() {
+            Some(history) => history,
+            None => {
+                f.render_widget(block, area);
+                return;
+            }
+        };
+
+        let my_name = self.my_name();
+        let names = self.member_names();
+        let context = ItemContext {
+            history,
+            my_name: my_name.as_deref(),
+            names: &names,
+            raw: self.show_raw,
+            rendered: &self.rendered,
+        };
+
+        let mut items: Vec<ListItem> = Vec::new();
+        if history.next_page_token.is_some() || history.is_loading {
+            let hint = if history.is_loading {
+                "loading..."
+            } else {
+                "scroll up for older messages"
+            };
+
+            items.push(ListItem::new(Span::styled(
+                hint,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let offset = items.len();
+
+        // position of the "new messages" divider among the loaded messages
+        let divider = self
+            .new_messages_from
+            .map(|first| history.messages.range(..first).count())
+            .filter(|&position| position < history.messages.len());
+
+        for (position, message) in history.messages.values().enumerate() {
+            if divider == Some(position) {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    "\u{2500}\u{2500} new messages \u{2500}\u{2500}",
+                    Style::default().fg(Color::LightRed),
+                ))));
+            }
+            items.push(message_item(message, &context, true));
+        }
+
+        for outgoing in &history.outgoing {
+            items.push(outgoing_item(outgoing, &context));
+        }
+
+        let selected = match self.message_index {
+            Some(index) if divider.is_some_and(|divider| index >= divider) => {
+                Some(index + offset + 1)
+            }
+            Some(index) => Some(index + offset),
+            None => items.len().checked_sub(1),
+        };
+
+        let highlight_style =
+            if self.current_focus == Focus::Messages && self.message_index.is_some() {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(highlight_style);
+
+        let mut state = ListState::default().with_selected(selected);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// The parent message followed by every loaded reply to it.
+    pub(super) fn render_thread(&self, f: &mut Frame, area: Rect, parent_id: MessageId) {
+        let block = self.pane_block("thread", Focus::Messages);
+
+        let history = match self.history() {
+            Some(history) => history,
+            None => {
+                f.render_widget(block, area);
+                return;
+            }
+        };
+
+        let my_name = self.my_name();
+        let names = self.member_names();
+        let context = ItemContext {
+            history,
+            my_name: my_name.as_deref(),
+            names: &names,
+            raw: self.show_raw,
+            rendered: &self.rendered,
+        };
+
+        let mut items: Vec<ListItem> = Vec::new();
+        match history.get(parent_id) {
+            Some(parent) => items.push(message_item(parent, &context, false)),
+            None => items.push(ListItem::new(Span::styled(
+                "(original message not loaded)",
+                Style::default().fg(Color::DarkGray),
+            ))),
+        }
+
+        let replies: Vec<ListItem> = history
+            .replies_to(parent_id)
+            .map(|reply| message_item(reply, &context, false))
+            .collect();
+
+        items.push(ListItem::new(Span::styled(
+            format!(
+                "\u{2500}\u{2500} {} {}",
+                replies.len(),
+                if replies.len() == 1 {
+                    "reply"
+                } else {
+                    "replies"
+                }
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        items.extend(replies);
+
+        f.render_widget(List::new(items).block(block), area);
+    }
+
+    pub(super) fn render_confirm_delete(&self, f: &mut Frame) {
+        let preview = self
+            .confirm_delete
+            .and_then(|message_id| self.history()?.get(message_id))
+            .map(|message| excerpt(&message.content))
+            .unwrap_or_default();
+
+        let text = Text::from(vec![
+            Line::from("Delete this message? (y/n)"),
+            Line::from(Span::styled(preview, Style::default().fg(Color::DarkGray))),
+        ]);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title("delete");
+
+        let area = centered_rect(f.size(), 50, 4);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(text).block(block).wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+}
+
+/// What every message item needs besides the message itself.
+struct ItemContext<'c> {
+    history: &'c History,
+    my_name: Option<&'c str>,
+    names: &'c MemberNames,
+    /// content as typed instead of rendered Markdown
+    raw: bool,
+    rendered: &'c RefCell<RenderedContents>,
+}
+
+fn message_item<'b>(
+    message: &'b Message,
+    context: &ItemContext,
+    show_parent: bool,
+) -> ListItem<'b> {
+    let is_mine = Some(message.owner.as_str()) == context.my_name;
+    let mentions_me = context
+        .my_name
+        .is_some_and(|my_name| mention::is_mention(message, my_name));
+    let author_style = if is_mine {
+        own_author_style()
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+
+    let mut lines = Vec::new();
+    if show_parent {
+        if let Some(parent_id) = history::reply_to(message) {
+            lines.push(parent_line(parent_id, context));
+        }
+    }
+
+    let mut header = vec![
+        Span::styled(author_label(&message.owner, context.names), author_style),
+        Span::raw("  "),
+        Span::styled(
+            format_time(&message.create_time),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if message.update_time.is_some() && message.update_time != message.create_time {
+        header.push(Span::styled(
+            " (edited)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if mentions_me {
+        header.push(Span::styled(
+            " @you",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if is_mine {
+        // anything in the history was accepted by the server
+        header.push(Span::styled(
+            " \u{2713}",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    lines.push(Line::from(header));
+    lines.extend(message_content_lines(message, context));
+    lines.extend(reactions::reaction_line(
+        message,
+        context.history,
+        context.my_name,
+    ));
+
+    ListItem::new(Text::from(lines))
+}
+
+fn outgoing_item<'b>(outgoing: &'b Outgoing, context: &ItemContext) -> ListItem<'b> {
+    let (status, status_style, content_style) = match &outgoing.state {
+        SendState::Pending => (
+            "sending...".to_string(),
+            Style::default().fg(Color::DarkGray),
+            Style::default().fg(Color::DarkGray),
+        ),
+        SendState::Failed(err) => (
+            format!("failed: {} (r: retry, Del: discard)", err),
+            Style::default().fg(Color::LightRed),
+            Style::default(),
+        ),
+    };
+
+    let mut lines = Vec::new();
+    if let Some(parent_id) = outgoing.reply_to {
+        lines.push(parent_line(parent_id, context));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled("you", own_author_style()),
+        Span::raw("  "),
+        Span::styled(status, status_style),
+    ]));
+    lines.extend(content_lines(&outgoing.content, content_style, context));
+
+    ListItem::new(Text::from(lines))
+}
+
+/// `↪ author: first line of the parent…`
+fn parent_line(parent_id: MessageId, context: &ItemContext) -> Line<'static> {
+    let text = match context.history.get(parent_id) {
+        Some(parent) => format!(
+            "{}: {}",
+            author_label(&parent.owner, context.names),
+            excerpt(&parent.content)
+        ),
+        None => "(original message not loaded)".to_string(),
+    };
+
+    Line::from(Span::styled(
+        format!("\u{21aa} {}", text),
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    ))
+}
+
+fn own_author_style() -> Style {
+    Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Content lines of a stored message, rendered again only after it was edited.
+fn message_content_lines(message: &Message, context: &ItemContext) -> Vec<Line<'static>> {
+    let mut rendered = context.rendered.borrow_mut();
+
+    if let Some(content) = rendered.get(&message.name) {
+        if content.update_time == message.update_time {
+            return content.lines.clone();
+        }
+    }
+
+    let lines = content_lines(&message.content, Style::default(), context);
+    rendered.insert(
+        message.name.clone(),
+        RenderedContent {
+            update_time: message.update_time.clone(),
+            lines: lines.clone(),
+        },
+    );
+
+    lines
+}
+
+fn content_lines(content: &str, style: Style, context: &ItemContext) -> Vec<Line<'static>> {
+    if !context.raw {
+        return markdown::render(content, style, context.names, context.my_name);
+    }
+
+    content
+        .lines()
+        .map(|line| Line::from(mention::spans(line, style, context.names, context.my_name)))
+        .collect()
+}
+
+pub(super) fn centered_rect(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(rows[1])[1]
+}
+
+/// First line of `content`, shortened to `EXCERPT_LENGTH` characters.
+pub(super) fn excerpt(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or_default();
+
+    if first_line.chars().count() > EXCERPT_LENGTH || content.lines().nth(1).is_some() {
+        let shortened: String = first_line.chars().take(EXCERPT_LENGTH).collect();
+        format!("{}\u{2026}", shortened)
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// `users/{id}` is shown as the last characters of the id until member names are known.
+pub(super) fn author_label(owner: &str, names: &MemberNames) -> String {
+    if let Some(display_name) = names.get(owner) {
+        return display_name.clone();
+    }
+
+    match owner.parse::<UserName>() {
+        Ok(name) => {
+            let id = name.user_id.to_string();
+            format!("user-{}", &id[id.len() - 6..])
+        }
+        Err(_) => owner.to_string(),
+    }
+}
+
+/// `HH:MM` in UTC.
+fn format_time(timestamp: &Option<Timestamp>) -> String {
+    match timestamp {
+        Some(timestamp) => {
+            let seconds_of_day = timestamp.seconds.rem_euclid(24 * 60 * 60);
+
+            format!(
+                "{:02}:{:02}",
+                seconds_of_day / 3600,
+                (seconds_of_day % 3600) / 60
+            )
+        }
+        None => String::new(),
+    }
+}
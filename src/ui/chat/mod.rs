@@ -0,0 +1,1204 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use arboard::Clipboard;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    prelude::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use ulid::Ulid;
+
+use crate::{
+    app_event::{AppEvent, EventSender, TaskResult},
+    app_state::AppState,
+    connection::ConnectionState,
+    rpc::{
+        channel::ChannelId,
+        client::RpcClient,
+        connect::event::ServerEvent,
+        message::MessageId,
+        name::{ChannelName, MessageName, ServerName, UserName},
+        server::ServerId,
+        ycchat::v1::models::{Message, Server, ServerMember},
+    },
+};
+
+use self::{
+    channels::Row,
+    composer::{Composer, Draft},
+    history::{History, Outgoing, SendState},
+    messages::RenderedContents,
+    reactions::{ReactionPicker, EMOJIS},
+    unread::Unread,
+};
+
+use super::{Scene, Ui};
+
+pub use self::channels::ChannelTree;
+
+mod channels;
+mod composer;
+mod highlight;
+mod history;
+mod markdown;
+mod mention;
+mod messages;
+mod reactions;
+mod unread;
+
+const PAGE_SIZE: i32 = 50;
+const SCROLL_STEP: usize = 10;
+
+/// The token of the next page; servers end a listing with either no token or an empty one.
+fn next_page(next_page_token: Option<String>) -> Option<String> {
+    next_page_token.filter(|token| !token.is_empty())
+}
+
+pub struct ChatUi<'a> {
+    app_state: Arc<Mutex<RefCell<AppState>>>,
+    current_focus: Focus,
+
+    servers: Vec<Server>,
+    server_index: usize,
+
+    channels: ChannelTree,
+    channel_index: usize,
+    members: HashMap<ServerId, Vec<ServerMember>>,
+    /// server of every channel listed so far, to sum up unread counts per server
+    channel_servers: HashMap<ChannelId, ServerId>,
+
+    histories: HashMap<ChannelId, History>,
+    /// `None` follows the newest message
+    message_index: Option<usize>,
+    /// parent message shown in the thread pane
+    thread: Option<MessageId>,
+    /// own message waiting for the user to confirm its deletion
+    confirm_delete: Option<MessageId>,
+    reaction_picker: Option<ReactionPicker>,
+    /// reactions added or removed whose request has not come back yet
+    pending_reactions: HashSet<(MessageId, String)>,
+    /// show message content as typed instead of rendering Markdown
+    show_raw: bool,
+    /// filled while drawing; dropped when the raw toggle or member names change
+    rendered: RefCell<RenderedContents>,
+
+    unread: HashMap<ChannelId, Unread>,
+    /// where the "new messages" divider goes in the shown channel
+    new_messages_from: Option<MessageId>,
+    pending_acknowledge: Option<(ChannelId, MessageId)>,
+    /// messages are only read while the chat scene is shown
+    is_visible: bool,
+
+    composer: Composer<'a>,
+    mention_index: usize,
+    /// `@query` whose suggestions were closed with Esc
+    mention_dismissed: Option<String>,
+    error_message: Option<String>,
+    /// confirmation shown in the status line until the next key press
+    notice: Option<String>,
+    clipboard: Option<Clipboard>,
+    /// message and index of the code block copied last, so copying again moves to the next one
+    last_copied: Option<(MessageId, usize)>,
+
+    connection_state: ConnectionState,
+    /// user the loaded state belongs to, so it is dropped after signing in as someone else
+    signed_in_as: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Servers,
+    Channels,
+    Messages,
+    Composer,
+}
+
+impl<'a> ChatUi<'a> {
+    pub fn new(app_state: Arc<Mutex<RefCell<AppState>>>) -> Self {
+        Self {
+            app_state,
+            current_focus: Focus::Servers,
+            servers: Vec::new(),
+            server_index: 0,
+            channels: ChannelTree::default(),
+            channel_index: 0,
+            members: HashMap::new(),
+            channel_servers: HashMap::new(),
+            histories: HashMap::new(),
+            message_index: None,
+            thread: None,
+            confirm_delete: None,
+            reaction_picker: None,
+            pending_reactions: HashSet::new(),
+            show_raw: false,
+            rendered: RefCell::new(RenderedContents::new()),
+            unread: HashMap::new(),
+            new_messages_from: None,
+            pending_acknowledge: None,
+            is_visible: false,
+            composer: Composer::new(),
+            mention_index: 0,
+            mention_dismissed: None,
+            error_message: None,
+            notice: None,
+            clipboard: None,
+            last_copied: None,
+            connection_state: ConnectionState::Connecting,
+            signed_in_as: None,
+        }
+    }
+
+    fn focus(&mut self, focus: Focus) {
+        self.composer.set_focus(focus == Focus::Composer);
+        self.current_focus = focus;
+    }
+
+    fn server_id(&self) -> Option<ServerId> {
+        let server = self.servers.get(self.server_index)?;
+
+        server
+            .name
+            .parse::<ServerName>()
+            .ok()
+            .map(|name| name.server_id)
+    }
+
+    fn channel_id(&self) -> Option<ChannelId> {
+        let channel = self.channels.get(self.channel_index)?;
+
+        channel
+            .name
+            .parse::<ChannelName>()
+            .ok()
+            .map(|name| name.channel_id)
+    }
+
+    fn history(&self) -> Option<&History> {
+        self.histories.get(&self.channel_id()?)
+    }
+
+    fn rpc_and_events(&self) -> (RpcClient, EventSender) {
+        let app_state = self.app_state.lock().unwrap();
+        let app_state = app_state.borrow();
+
+        (app_state.rpc.clone(), app_state.events.clone())
+    }
+
+    fn load_servers(&self) {
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move { TaskResult::Servers(channels::fetch_servers(rpc).await) });
+    }
+
+    fn select_server(&mut self, index: usize) {
+        self.server_index = index;
+        self.channels = ChannelTree::default();
+        self.channel_index = 0;
+        self.message_index = None;
+
+        let server_id = match self.server_id() {
+            Some(server_id) => server_id,
+            None => return,
+        };
+        self.load_members(server_id);
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = channels::fetch_channel_tree(rpc, server_id).await;
+
+            TaskResult::Channels(server_id, result)
+        });
+    }
+
+    fn select_channel(&mut self, index: usize) {
+        self.channel_index = index;
+        self.message_index = None;
+        self.thread = None;
+        self.confirm_delete = None;
+        self.reaction_picker = None;
+        self.composer.cancel();
+        self.new_messages_from = self
+            .channel_id()
+            .and_then(|channel_id| self.unread(channel_id).first);
+
+        if let Some(channel) = self.channels.get(index) {
+            self.composer
+                .set_placeholder(format!("Message #{}", channel.display_name));
+        }
+
+        if let Some(channel_id) = self.channel_id() {
+            if !self.histories.contains_key(&channel_id) {
+                self.load_messages(channel_id, None);
+            }
+        }
+    }
+
+    /// Loads the newest page when `page_token` is `None`, otherwise the page before it.
+    fn load_messages(&mut self, channel_id: ChannelId, page_token: Option<String>) {
+        let history = self.histories.entry(channel_id).or_default();
+        if history.is_loading {
+            return;
+        }
+        history.is_loading = true;
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = rpc
+                .message()
+                .list_messages(channel_id, PAGE_SIZE, page_token)
+                .await;
+
+            TaskResult::Messages(channel_id, result)
+        });
+    }
+
+    fn load_older_messages(&mut self) {
+        let channel_id = match self.channel_id() {
+            Some(channel_id) => channel_id,
+            None => return,
+        };
+
+        let page_token = self
+            .histories
+            .get(&channel_id)
+            .and_then(|history| history.next_page_token.clone());
+
+        if page_token.is_some() {
+            self.load_messages(channel_id, page_token);
+        }
+    }
+
+    fn move_message_index(&mut self, up: bool, step: usize) {
+        let len = match self.history() {
+            Some(history) => history.len(),
+            None => return,
+        };
+        if len == 0 {
+            return;
+        }
+
+        let index = self.message_index.unwrap_or(len - 1);
+
+        self.message_index = if up {
+            if index == 0 {
+                self.load_older_messages();
+            }
+
+            Some(index.saturating_sub(step))
+        } else if index + step >= len {
+            None
+        } else {
+            Some(index + step)
+        };
+    }
+
+    fn send(&mut self, draft: Draft) {
+        let channel_id = match self.channel_id() {
+            Some(channel_id) => channel_id,
+            None => return,
+        };
+
+        let (content, reply_to) = match draft {
+            Draft::New { content, reply_to } => (content, reply_to),
+            Draft::Edit {
+                message_id,
+                content,
+            } => {
+                self.edit_message(channel_id, message_id, content);
+                return;
+            }
+        };
+
+        let outgoing = Outgoing {
+            local_id: Ulid::new(),
+            content,
+            reply_to,
+            state: SendState::Pending,
+        };
+
+        self.speech(channel_id, &outgoing);
+        self.histories
+            .entry(channel_id)
+            .or_default()
+            .outgoing
+            .push(outgoing);
+        self.message_index = None;
+    }
+
+    /// The loaded message under the cursor; outgoing messages are not selectable here.
+    fn selected_message(&self) -> Option<(MessageId, &Message)> {
+        let message = self.history()?.message_at(self.message_index?)?;
+        let message_id = message.name.parse::<MessageName>().ok()?.message_id;
+
+        Some((message_id, message))
+    }
+
+    /// The selected message, if the signed-in user wrote it.
+    fn selected_own_message(&self) -> Option<(MessageId, &Message)> {
+        let my_name = self.my_name()?;
+
+        self.selected_message()
+            .filter(|(_, message)| message.owner == my_name)
+    }
+
+    fn edit_selected(&mut self) {
+        let (message_id, content) = match self.selected_own_message() {
+            Some((message_id, message)) => (message_id, message.content.clone()),
+            None => return,
+        };
+
+        self.composer.start_edit(message_id, &content);
+        self.focus(Focus::Composer);
+    }
+
+    /// Shows the new content right away and puts the original back if the update fails.
+    fn edit_message(&mut self, channel_id: ChannelId, message_id: MessageId, content: String) {
+        let message = match self
+            .histories
+            .get_mut(&channel_id)
+            .and_then(|history| history.messages.get_mut(&message_id))
+        {
+            Some(message) if message.content != content => message,
+            _ => return,
+        };
+
+        let original = message.clone();
+        message.content = content;
+        let updated = message.clone();
+        // the update time only changes once the server confirms; a failed edit is put
+        // back through `insert_message`, which drops the entry again
+        self.rendered.get_mut().remove(&updated.name);
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = rpc.message().update_message(updated).await;
+
+            TaskResult::MessageUpdated {
+                channel_id,
+                original,
+                result,
+            }
+        });
+    }
+
+    /// Removes the message right away and puts it back if the deletion fails.
+    fn delete_message(&mut self, message_id: MessageId) {
+        let channel_id = match self.channel_id() {
+            Some(channel_id) => channel_id,
+            None => return,
+        };
+
+        let original = match self.remove_message(channel_id, message_id) {
+            Some(original) => original,
+            None => return,
+        };
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = rpc.message().delete_message(message_id).await;
+
+            TaskResult::MessageDeleted {
+                channel_id,
+                original,
+                result,
+            }
+        });
+    }
+
+    /// Copies a code block of the selected message; pressing again copies the next one.
+    fn copy_code_block(&mut self) {
+        let (message_id, blocks) = match self.selected_message() {
+            Some((message_id, message)) => (message_id, markdown::code_blocks(&message.content)),
+            None => return,
+        };
+        if blocks.is_empty() {
+            self.notice = Some("No code block in this message".to_string());
+            return;
+        }
+
+        let index = match self.last_copied {
+            Some((last_id, last_index)) if last_id == message_id => (last_index + 1) % blocks.len(),
+            _ => 0,
+        };
+
+        // kept alive: on X11 the copied text is gone once the clipboard is dropped
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => match Clipboard::new() {
+                Ok(clipboard) => self.clipboard.insert(clipboard),
+                Err(err) => {
+                    self.error_message = Some(format!("Clipboard unavailable: {}", err));
+                    return;
+                }
+            },
+        };
+
+        match clipboard.set_text(blocks[index].clone()) {
+            Ok(()) => {
+                self.last_copied = Some((message_id, index));
+                self.notice = Some(match blocks.len() {
+                    1 => "Copied code block".to_string(),
+                    count => format!("Copied code block {} of {}", index + 1, count),
+                });
+            }
+            Err(err) => self.error_message = Some(format!("Could not copy: {}", err)),
+        }
+    }
+
+    /// Attaches the selected message to the composer.
+    fn reply_to_selected(&mut self) {
+        let (message_id, preview) = match self.selected_message() {
+            Some((message_id, message)) => (
+                message_id,
+                format!(
+                    "{}: {}",
+                    messages::author_label(&message.owner, &self.member_names()),
+                    messages::excerpt(&message.content)
+                ),
+            ),
+            None => return,
+        };
+
+        self.composer.set_reply(message_id, preview);
+        self.focus(Focus::Composer);
+    }
+
+    /// Opens the thread of the selected message, i.e. of its parent if it is a reply.
+    fn toggle_thread(&mut self) {
+        if self.thread.take().is_some() {
+            return;
+        }
+
+        self.thread = self
+            .selected_message()
+            .map(|(message_id, message)| history::reply_to(message).unwrap_or(message_id));
+    }
+
+    /// Sends the selected message again if it failed.
+    fn retry_selected(&mut self) {
+        let (channel_id, index) = match (self.channel_id(), self.message_index) {
+            (Some(channel_id), Some(index)) => (channel_id, index),
+            _ => return,
+        };
+
+        let (rpc, events) = self.rpc_and_events();
+
+        let outgoing = match self
+            .histories
+            .get_mut(&channel_id)
+            .and_then(|history| history.outgoing_at(index))
+        {
+            Some(outgoing) if matches!(outgoing.state, SendState::Failed(_)) => outgoing,
+            _ => return,
+        };
+
+        outgoing.state = SendState::Pending;
+        spawn_speech(rpc, events, channel_id, outgoing);
+    }
+
+    /// Drops the selected message if it failed to send.
+    fn discard_selected(&mut self) {
+        let (channel_id, index) = match (self.channel_id(), self.message_index) {
+            (Some(channel_id), Some(index)) => (channel_id, index),
+            _ => return,
+        };
+
+        if let Some(history) = self.histories.get_mut(&channel_id) {
+            let local_id = match history.outgoing_at(index) {
+                Some(outgoing) if matches!(outgoing.state, SendState::Failed(_)) => {
+                    outgoing.local_id
+                }
+                _ => return,
+            };
+
+            history.take_outgoing(local_id);
+            self.message_index = None;
+        }
+    }
+
+    fn speech(&self, channel_id: ChannelId, outgoing: &Outgoing) {
+        let (rpc, events) = self.rpc_and_events();
+
+        spawn_speech(rpc, events, channel_id, outgoing);
+    }
+
+    /// Keeps the selected message in place when a message is inserted before it.
+    fn insert_message(&mut self, channel_id: ChannelId, message: Message) {
+        // an edit shown before the server confirms it keeps the old update time
+        self.rendered.get_mut().remove(&message.name);
+
+        let position = self
+            .histories
+            .entry(channel_id)
+            .or_default()
+            .insert(message);
+
+        if self.channel_id() != Some(channel_id) {
+            return;
+        }
+
+        if let (Some(position), Some(index)) = (position, &mut self.message_index) {
+            if position <= *index {
+                *index += 1;
+            }
+        }
+    }
+
+    fn remove_message(&mut self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        let (position, message) = self.histories.get_mut(&channel_id)?.remove(message_id)?;
+        self.rendered.get_mut().remove(&message.name);
+
+        if self.channel_id() == Some(channel_id) {
+            if let Some(index) = &mut self.message_index {
+                if position < *index {
+                    *index -= 1;
+                } else if position == *index {
+                    self.message_index = None;
+                }
+            }
+        }
+
+        if self.thread == Some(message_id) {
+            self.thread = None;
+        }
+
+        Some(message)
+    }
+
+    fn server_event_handle(&mut self, event: ServerEvent) {
+        match event {
+            ServerEvent::MessageCreated {
+                channel_id,
+                message,
+            } => {
+                let is_loaded = message
+                    .name
+                    .parse::<MessageName>()
+                    .ok()
+                    .and_then(|name| self.histories.get(&channel_id)?.get(name.message_id))
+                    .is_some();
+                if !is_loaded {
+                    self.add_unread(channel_id, &message);
+                }
+
+                if self.histories.contains_key(&channel_id) {
+                    self.insert_message(channel_id, message);
+                }
+            }
+            ServerEvent::MessageUpdated {
+                channel_id,
+                message,
+            } => {
+                // channels that were never opened load their history when they are
+                if self.histories.contains_key(&channel_id) {
+                    self.insert_message(channel_id, message);
+                }
+            }
+        }
+    }
+
+    fn connection_event_handle(&mut self, state: ConnectionState) {
+        let is_reconnected = state == ConnectionState::Connected
+            && matches!(self.connection_state, ConnectionState::Reconnecting { .. });
+
+        self.connection_state = state;
+
+        if is_reconnected {
+            self.backfill();
+        }
+    }
+
+    /// Fetches what every loaded channel missed while the stream was down.
+    fn backfill(&mut self) {
+        let (rpc, events) = self.rpc_and_events();
+
+        for (&channel_id, history) in &self.histories {
+            let last_seen = match history.last_seen() {
+                Some(last_seen) => last_seen,
+                None => continue,
+            };
+
+            let rpc = rpc.clone();
+            events.spawn(async move {
+                let result = history::fetch_since(rpc, channel_id, last_seen, PAGE_SIZE).await;
+
+                TaskResult::Backfill(channel_id, result)
+            });
+        }
+    }
+
+    fn task_handle(&mut self, result: TaskResult) {
+        match result {
+            TaskResult::Servers(Ok(servers)) => {
+                self.servers = servers;
+                self.error_message = None;
+                self.select_server(0);
+            }
+            TaskResult::Channels(server_id, Ok(tree)) if self.server_id() == Some(server_id) => {
+                self.channels = tree;
+                for channel in &self.channels.channels {
+                    if let Ok(name) = channel.name.parse::<ChannelName>() {
+                        self.channel_servers.insert(name.channel_id, server_id);
+                    }
+                }
+                self.select_channel(0);
+            }
+            TaskResult::Messages(channel_id, Ok(response)) => {
+                let message_ids = message_ids(&response.messages);
+                for message in response.messages {
+                    self.insert_message(channel_id, message);
+                }
+                self.load_reactions(channel_id, message_ids);
+
+                let history = self.histories.entry(channel_id).or_default();
+                history.next_page_token = next_page(response.next_page_token);
+                history.is_loading = false;
+            }
+            TaskResult::MessageUpdated {
+                channel_id,
+                original,
+                result,
+            } => match result {
+                Ok(message) => self.insert_message(channel_id, message),
+                Err(err) => {
+                    self.insert_message(channel_id, original);
+                    self.error_message = Some(format!("Could not edit the message: {}", err));
+                }
+            },
+            TaskResult::MessageDeleted {
+                channel_id,
+                original,
+                result: Err(err),
+            } => {
+                self.insert_message(channel_id, original);
+                self.error_message = Some(format!("Could not delete the message: {}", err));
+            }
+            TaskResult::Backfill(channel_id, Ok(messages)) => {
+                let message_ids = message_ids(&messages);
+                for message in messages {
+                    self.insert_message(channel_id, message);
+                }
+                self.load_reactions(channel_id, message_ids);
+            }
+            TaskResult::Reactions {
+                channel_id,
+                reactions,
+                error,
+            } => {
+                let history = self.histories.entry(channel_id).or_default();
+                history.reactions.extend(reactions);
+
+                if let Some(err) = error {
+                    self.error_message = Some(format!("Could not load some reactions: {}", err));
+                }
+            }
+            TaskResult::ReactionAdded {
+                channel_id,
+                message_id,
+                emoji,
+                result,
+            } => {
+                self.pending_reactions.remove(&(message_id, emoji));
+
+                match result {
+                    Ok(reaction) => {
+                        let history = self.histories.entry(channel_id).or_default();
+                        history
+                            .reactions
+                            .entry(message_id)
+                            .or_default()
+                            .push(reaction);
+                    }
+                    Err(err) => self.error_message = Some(format!("Could not react: {}", err)),
+                }
+            }
+            TaskResult::ReactionDeleted {
+                channel_id,
+                message_id,
+                original,
+                result,
+            } => {
+                self.pending_reactions
+                    .remove(&(message_id, original.emoji.clone()));
+
+                if let Err(err) = result {
+                    let history = self.histories.entry(channel_id).or_default();
+                    history
+                        .reactions
+                        .entry(message_id)
+                        .or_default()
+                        .push(original);
+                    self.error_message = Some(format!("Could not remove the reaction: {}", err));
+                }
+            }
+            TaskResult::Members(server_id, Ok(members)) => {
+                self.members.insert(server_id, members);
+                // mentions show display names once they are known
+                self.rendered.get_mut().clear();
+            }
+            TaskResult::Members(_, Err(err)) => {
+                self.error_message = Some(format!("Could not load members: {}", err));
+            }
+            TaskResult::AcknowledgeDue(channel_id, message_id) => {
+                self.acknowledge(channel_id, message_id);
+            }
+            TaskResult::Acknowledged {
+                channel_id,
+                message_id,
+                result,
+            } => match result {
+                Ok(()) => {
+                    let history = self.histories.entry(channel_id).or_default();
+                    history.acknowledged = history.acknowledged.max(Some(message_id));
+                }
+                Err(err) => {
+                    tracing::warn!(%channel_id, %message_id, error = %err, "acknowledge failed");
+                }
+            },
+            TaskResult::Backfill(_, Err(err)) => {
+                self.error_message = Some(format!("Could not load missed messages: {}", err));
+            }
+            TaskResult::Messages(channel_id, Err(err)) => {
+                if let Some(history) = self.histories.get_mut(&channel_id) {
+                    history.is_loading = false;
+                }
+
+                self.error_message = Some(err.to_string());
+            }
+            TaskResult::Speech {
+                channel_id,
+                local_id,
+                result,
+            } => {
+                let history = self.histories.entry(channel_id).or_default();
+
+                match result {
+                    Ok(response) => {
+                        history.take_outgoing(local_id);
+
+                        if let Some(message) = response.message {
+                            self.insert_message(channel_id, message);
+                        }
+                    }
+                    Err(err) => {
+                        if let Some(outgoing) = history
+                            .outgoing
+                            .iter_mut()
+                            .find(|outgoing| outgoing.local_id == local_id)
+                        {
+                            outgoing.state = SendState::Failed(err.to_string());
+                        }
+                    }
+                }
+            }
+            TaskResult::Servers(Err(err)) | TaskResult::Channels(_, Err(err)) => {
+                self.error_message = Some(err.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn render_servers(&self, f: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .servers
+            .iter()
+            .map(|server| {
+                let server_id = server
+                    .name
+                    .parse::<ServerName>()
+                    .ok()
+                    .map(|name| name.server_id);
+                let unread = self
+                    .channel_servers
+                    .iter()
+                    .filter(|(_, &channel_server)| Some(channel_server) == server_id)
+                    .fold(Unread::default(), |total, (&channel_id, _)| {
+                        let unread = self.unread(channel_id);
+                        Unread {
+                            count: total.count + unread.count,
+                            mentions: total.mentions + unread.mentions,
+                            first: None,
+                        }
+                    });
+
+                ListItem::new(unread_line(server.display_name.clone(), unread))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(self.pane_block("servers", Focus::Servers))
+            .highlight_style(Style::default().bg(Color::LightCyan));
+
+        let mut state = ListState::default().with_selected(Some(self.server_index));
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_channels(&self, f: &mut Frame, area: Rect) {
+        let title = match self.servers.get(self.server_index) {
+            Some(server) => server.display_name.clone(),
+            None => "channels".to_string(),
+        };
+
+        let rows = self.channels.rows();
+        let selected = rows
+            .iter()
+            .position(|row| matches!(row, Row::Channel(index) if *index == self.channel_index));
+
+        let items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|row| match row {
+                Row::Heading(heading) => ListItem::new(Line::from(Span::styled(
+                    heading.to_uppercase(),
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                ))),
+                Row::Channel(index) => {
+                    let channel = &self.channels.channels[index];
+                    let unread = match channel.name.parse::<ChannelName>() {
+                        Ok(name) => self.unread(name.channel_id),
+                        Err(_) => Unread::default(),
+                    };
+
+                    ListItem::new(unread_line(format!("  # {}", channel.display_name), unread))
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(self.pane_block(&title, Focus::Channels))
+            .highlight_style(Style::default().bg(Color::LightCyan));
+
+        let mut state = ListState::default().with_selected(selected);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn pane_block<'b>(&self, title: &'b str, focus: Focus) -> Block<'b> {
+        let style = if self.current_focus == focus {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(style)
+            .title(title)
+    }
+
+    fn my_name(&self) -> Option<String> {
+        let app_state = self.app_state.lock().unwrap();
+        let app_state = app_state.borrow();
+        let user_state = app_state.user.as_ref()?;
+
+        let user_id = user_state.sign_in_response.user_id.parse().ok()?;
+
+        Some(UserName::new(user_id).to_string())
+    }
+}
+
+impl<'a> Ui for ChatUi<'a> {
+    fn ui(&self, f: &mut Frame) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20), // servers
+                Constraint::Percentage(20), // channels
+                Constraint::Percentage(60), // messages
+            ])
+            .split(f.size());
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),                         // messages
+                Constraint::Length(self.composer.height()), // composer
+                Constraint::Length(1),                      // error message
+            ])
+            .split(columns[2]);
+
+        self.render_servers(f, columns[0]);
+        self.render_channels(f, columns[1]);
+        match self.thread {
+            Some(parent_id) => {
+                let panes = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(rows[0]);
+
+                self.render_messages(f, panes[0]);
+                self.render_thread(f, panes[1], parent_id);
+            }
+            None => self.render_messages(f, rows[0]),
+        }
+        self.composer.render(f, rows[1]);
+        self.render_mention_popup(f, rows[1]);
+
+        if self.confirm_delete.is_some() {
+            self.render_confirm_delete(f);
+        }
+        if self.reaction_picker.is_some() {
+            self.render_reaction_picker(f);
+        }
+
+        let (connection, connection_style) = match self.connection_state {
+            ConnectionState::Connecting => (
+                "connecting...".to_string(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            ConnectionState::Connected => (
+                "\u{25cf} online".to_string(),
+                Style::default().fg(Color::Green),
+            ),
+            ConnectionState::Reconnecting { attempt } => (
+                format!("\u{25cb} reconnecting (attempt {})", attempt),
+                Style::default().fg(Color::Yellow),
+            ),
+        };
+
+        let status = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(connection.chars().count() as u16),
+            ])
+            .split(rows[2]);
+
+        let (message, message_style) = match (&self.error_message, &self.notice) {
+            (Some(err_message), _) => (err_message.clone(), Style::default().fg(Color::LightRed)),
+            (None, Some(notice)) => (notice.clone(), Style::default().fg(Color::DarkGray)),
+            (None, None) => (String::new(), Style::default()),
+        };
+
+        f.render_widget(
+            Paragraph::new(Text::styled(message, message_style)),
+            status[0],
+        );
+        f.render_widget(
+            Paragraph::new(Text::styled(connection, connection_style)),
+            status[1],
+        );
+    }
+
+    fn event_handle<'me>(
+        &'me mut self,
+        event: std::io::Result<crossterm::event::Event>,
+    ) -> Pin<Box<dyn std::future::Future<Output = io::Result<Scene>> + Send + 'me>> {
+        let me: &'me mut ChatUi = self;
+
+        Box::pin(async move {
+            let key = match event? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => return Ok(Scene::Chat),
+            };
+            me.notice = None;
+
+            if let Some(message_id) = me.confirm_delete {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        me.confirm_delete = None;
+                        me.delete_message(message_id);
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => me.confirm_delete = None,
+                    _ => {}
+                }
+
+                return Ok(Scene::Chat);
+            }
+
+            if let Some(picker) = &mut me.reaction_picker {
+                match key.code {
+                    KeyCode::Left | KeyCode::BackTab => {
+                        picker.index = (picker.index + EMOJIS.len() - 1) % EMOJIS.len();
+                    }
+                    KeyCode::Right | KeyCode::Tab => {
+                        picker.index = (picker.index + 1) % EMOJIS.len();
+                    }
+                    KeyCode::Enter => {
+                        let (message_id, emoji) = (picker.message_id, EMOJIS[picker.index]);
+                        me.reaction_picker = None;
+                        me.toggle_reaction(message_id, emoji);
+                    }
+                    KeyCode::Esc => me.reaction_picker = None,
+                    _ => {}
+                }
+
+                return Ok(Scene::Chat);
+            }
+
+            match (key.code, me.current_focus) {
+                (
+                    KeyCode::Up | KeyCode::Down | KeyCode::Tab | KeyCode::Enter | KeyCode::Esc,
+                    Focus::Composer,
+                ) if me.is_mention_popup_open() => me.mention_key(key.code),
+                (KeyCode::Esc, Focus::Composer) if me.composer.has_target() => {
+                    me.composer.cancel();
+                }
+                (KeyCode::Esc, Focus::Composer) => me.focus(Focus::Messages),
+                (KeyCode::Esc, Focus::Messages) if me.thread.is_some() => me.thread = None,
+                (KeyCode::Esc, _) => return Ok(Scene::AfterSignIn),
+                (KeyCode::Tab, _) => {
+                    let focus = match me.current_focus {
+                        Focus::Servers => Focus::Channels,
+                        Focus::Channels => Focus::Messages,
+                        Focus::Messages => Focus::Composer,
+                        Focus::Composer => Focus::Servers,
+                    };
+
+                    me.focus(focus);
+                }
+                (KeyCode::BackTab, _) => {
+                    let focus = match me.current_focus {
+                        Focus::Servers => Focus::Composer,
+                        Focus::Channels => Focus::Servers,
+                        Focus::Messages => Focus::Channels,
+                        Focus::Composer => Focus::Messages,
+                    };
+
+                    me.focus(focus);
+                }
+                (KeyCode::Up, Focus::Servers) if me.server_index > 0 => {
+                    me.select_server(me.server_index - 1)
+                }
+                (KeyCode::Down, Focus::Servers) if me.server_index + 1 < me.servers.len() => {
+                    me.select_server(me.server_index + 1)
+                }
+                (KeyCode::Up, Focus::Channels) if me.channel_index > 0 => {
+                    me.select_channel(me.channel_index - 1)
+                }
+                (KeyCode::Down, Focus::Channels) if me.channel_index + 1 < me.channels.len() => {
+                    me.select_channel(me.channel_index + 1)
+                }
+                (KeyCode::Up, Focus::Messages) => me.move_message_index(true, 1),
+                (KeyCode::Down, Focus::Messages) => me.move_message_index(false, 1),
+                (KeyCode::PageUp, Focus::Messages) => me.move_message_index(true, SCROLL_STEP),
+                (KeyCode::PageDown, Focus::Messages) => me.move_message_index(false, SCROLL_STEP),
+                (KeyCode::End, Focus::Messages) => me.message_index = None,
+                (KeyCode::Char('r'), Focus::Messages) if me.selected_message().is_some() => {
+                    me.reply_to_selected()
+                }
+                (KeyCode::Char('r'), Focus::Messages) => me.retry_selected(),
+                (KeyCode::Char('t'), Focus::Messages) => me.toggle_thread(),
+                (KeyCode::Char('+'), Focus::Messages) => me.open_reaction_picker(),
+                (KeyCode::Char('m'), Focus::Messages) => {
+                    me.show_raw = !me.show_raw;
+                    me.rendered.get_mut().clear();
+                }
+                (KeyCode::Char('y'), Focus::Messages) => me.copy_code_block(),
+                (KeyCode::Char('e'), Focus::Messages) => me.edit_selected(),
+                (KeyCode::Char('d') | KeyCode::Delete, Focus::Messages) => {
+                    match me.selected_own_message() {
+                        Some((message_id, _)) => me.confirm_delete = Some(message_id),
+                        None => me.discard_selected(),
+                    }
+                }
+                (_, Focus::Composer) => {
+                    me.mention_index = 0;
+                    if let Some(draft) = me.composer.input(key) {
+                        me.send(draft);
+                    }
+                }
+                _ => {}
+            }
+
+            me.mark_read();
+
+            Ok(Scene::Chat)
+        })
+    }
+
+    fn on_enter(&mut self) {
+        let signed_in_as = self.my_name();
+        if signed_in_as != self.signed_in_as {
+            let connection_state = self.connection_state;
+
+            *self = Self::new(self.app_state.clone());
+            self.connection_state = connection_state;
+            self.signed_in_as = signed_in_as;
+        }
+
+        self.error_message = None;
+
+        self.is_visible = true;
+        if self.servers.is_empty() {
+            self.load_servers();
+        }
+        self.mark_read();
+    }
+
+    fn on_leave(&mut self) {
+        self.is_visible = false;
+        self.pending_acknowledge = None;
+    }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        match event {
+            AppEvent::Task(result) => self.task_handle(result),
+            AppEvent::Server(event) => self.server_event_handle(event),
+            AppEvent::Connection(state) => self.connection_event_handle(state),
+        }
+
+        self.mark_read();
+
+        None
+    }
+}
+
+fn spawn_speech(rpc: RpcClient, events: EventSender, channel_id: ChannelId, outgoing: &Outgoing) {
+    let local_id = outgoing.local_id;
+    let content = outgoing.content.clone();
+    let reply_to = outgoing.reply_to;
+
+    events.spawn(async move {
+        let result = rpc.channel().speech(channel_id, content, reply_to).await;
+
+        TaskResult::Speech {
+            channel_id,
+            local_id,
+            result,
+        }
+    });
+}
+
+fn message_ids(messages: &[Message]) -> Vec<MessageId> {
+    messages
+        .iter()
+        .filter_map(|message| message.name.parse::<MessageName>().ok())
+        .map(|name| name.message_id)
+        .collect()
+}
+
+/// `label (3) @1`: bold while there are unread messages, with a badge for mentions.
+fn unread_line(label: String, unread: Unread) -> Line<'static> {
+    if unread.count == 0 {
+        return Line::from(label);
+    }
+
+    let mut spans = vec![
+        Span::styled(label, Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!(" ({})", unread.count)),
+    ];
+    if unread.mentions > 0 {
+        spans.push(Span::styled(
+            format!(" @{}", unread.mentions),
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    Line::from(spans)
+}
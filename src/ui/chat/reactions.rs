@@ -0,0 +1,299 @@
+use futures::{stream, StreamExt};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app_event::TaskResult;
+use crate::rpc::{
+    channel::ChannelId,
+    client::RpcClient,
+    error::ClientError,
+    message::MessageId,
+    name::ReactionName,
+    ycchat::v1::models::{Message, Reaction},
+};
+
+use super::history::History;
+use super::messages::centered_rect;
+use super::{next_page, ChatUi, PAGE_SIZE};
+
+// messages whose reactions are fetched at the same time
+const CONCURRENT_FETCHES: usize = 4;
+
+/// Emojis offered by the picker.
+pub const EMOJIS: [&str; 8] = [
+    "\u{1f44d}", // thumbs up
+    "\u{1f44e}", // thumbs down
+    "\u{1f604}", // smile
+    "\u{1f389}", // party popper
+    "\u{1f615}", // confused
+    "\u{2764}",  // heart
+    "\u{1f680}", // rocket
+    "\u{1f440}", // eyes
+];
+
+pub struct ReactionPicker {
+    pub message_id: MessageId,
+    pub index: usize,
+}
+
+/// Reactions with the same emoji, in the order the emoji was first used.
+pub struct ReactionCount<'r> {
+    pub emoji: &'r str,
+    pub count: usize,
+    /// the signed-in user's reaction, if they added this emoji
+    pub mine: Option<&'r Reaction>,
+}
+
+pub fn aggregate<'r>(reactions: &'r [Reaction], my_name: Option<&str>) -> Vec<ReactionCount<'r>> {
+    let mut counts: Vec<ReactionCount> = Vec::new();
+
+    for reaction in reactions {
+        let is_mine = Some(reaction.owner.as_str()) == my_name;
+
+        match counts
+            .iter_mut()
+            .find(|count| count.emoji == reaction.emoji)
+        {
+            Some(count) => {
+                count.count += 1;
+                if is_mine {
+                    count.mine = Some(reaction);
+                }
+            }
+            None => counts.push(ReactionCount {
+                emoji: &reaction.emoji,
+                count: 1,
+                mine: is_mine.then_some(reaction),
+            }),
+        }
+    }
+
+    counts
+}
+
+/// `👍 2  🎉 1`, with the user's own reactions highlighted.
+pub fn reaction_line(
+    message: &Message,
+    history: &History,
+    my_name: Option<&str>,
+) -> Option<Line<'static>> {
+    let counts = aggregate(history.reactions_of(message), my_name);
+    if counts.is_empty() {
+        return None;
+    }
+
+    let spans = counts
+        .iter()
+        .flat_map(|count| {
+            let style = if count.mine.is_some() {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+
+            [
+                Span::styled(format!("{} {}", count.emoji, count.count), style),
+                Span::raw("  "),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    Some(Line::from(spans))
+}
+
+/// Fetches the reactions of every message in `message_ids`, a few messages at a time.
+/// Reactions that loaded are kept when other messages fail; the first error comes with them.
+pub async fn fetch_reactions(
+    rpc: RpcClient,
+    message_ids: Vec<MessageId>,
+) -> (Vec<(MessageId, Vec<Reaction>)>, Option<ClientError>) {
+    let results: Vec<_> = stream::iter(message_ids)
+        .map(|message_id| {
+            let rpc = rpc.clone();
+            async move { (message_id, fetch_message_reactions(rpc, message_id).await) }
+        })
+        .buffer_unordered(CONCURRENT_FETCHES)
+        .collect()
+        .await;
+
+    let mut all = Vec::with_capacity(results.len());
+    let mut error = None;
+
+    for (message_id, result) in results {
+        match result {
+            Ok(reactions) => all.push((message_id, reactions)),
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    (all, error)
+}
+
+/// Pages through the reactions of one message.
+async fn fetch_message_reactions(
+    rpc: RpcClient,
+    message_id: MessageId,
+) -> Result<Vec<Reaction>, ClientError> {
+    let mut reaction_service = rpc.reaction();
+    let mut reactions = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let response = reaction_service
+            .list_reactions(message_id, PAGE_SIZE, page_token)
+            .await?;
+
+        reactions.extend(response.reactions);
+
+        match next_page(response.next_page_token) {
+            Some(next_page_token) => page_token = Some(next_page_token),
+            None => break,
+        }
+    }
+
+    Ok(reactions)
+}
+
+impl<'a> ChatUi<'a> {
+    pub(super) fn load_reactions(&self, channel_id: ChannelId, message_ids: Vec<MessageId>) {
+        if message_ids.is_empty() {
+            return;
+        }
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let (reactions, error) = fetch_reactions(rpc, message_ids).await;
+
+            TaskResult::Reactions {
+                channel_id,
+                reactions,
+                error,
+            }
+        });
+    }
+
+    pub(super) fn open_reaction_picker(&mut self) {
+        if let Some((message_id, _)) = self.selected_message() {
+            self.reaction_picker = Some(ReactionPicker {
+                message_id,
+                index: 0,
+            });
+        }
+    }
+
+    /// Adds the emoji, or removes it if the user already reacted with it. Ignored while the
+    /// previous toggle of the same emoji is still on its way.
+    pub(super) fn toggle_reaction(&mut self, message_id: MessageId, emoji: &str) {
+        let channel_id = match self.channel_id() {
+            Some(channel_id) => channel_id,
+            None => return,
+        };
+        let my_name = self.my_name();
+
+        let toggle = (message_id, emoji.to_string());
+        if self.pending_reactions.contains(&toggle) {
+            return;
+        }
+
+        let (rpc, events) = self.rpc_and_events();
+
+        let history = self.histories.entry(channel_id).or_default();
+        let reactions = history.reactions.entry(message_id).or_default();
+
+        let mine = reactions.iter().position(|reaction| {
+            reaction.emoji == emoji && Some(reaction.owner.as_str()) == my_name.as_deref()
+        });
+
+        match mine {
+            Some(position) => {
+                // removed right away and put back if the deletion fails
+                let original = reactions.remove(position);
+                let name = match original.name.parse::<ReactionName>() {
+                    Ok(name) => name,
+                    Err(_) => return,
+                };
+                self.pending_reactions.insert(toggle);
+
+                events.spawn(async move {
+                    let result = rpc.reaction().delete_reaction(name).await;
+
+                    TaskResult::ReactionDeleted {
+                        channel_id,
+                        message_id,
+                        original,
+                        result,
+                    }
+                });
+            }
+            None => {
+                let emoji = emoji.to_string();
+                self.pending_reactions.insert(toggle);
+
+                events.spawn(async move {
+                    let result = rpc.reaction().add_reaction(message_id, emoji.clone()).await;
+
+                    TaskResult::ReactionAdded {
+                        channel_id,
+                        message_id,
+                        emoji,
+                        result,
+                    }
+                });
+            }
+        }
+    }
+
+    pub(super) fn render_reaction_picker(&self, f: &mut Frame) {
+        let picker = match &self.reaction_picker {
+            Some(picker) => picker,
+            None => return,
+        };
+
+        let my_name = self.my_name();
+        let mine: Vec<&str> = self
+            .history()
+            .and_then(|history| history.reactions.get(&picker.message_id))
+            .map(|reactions| {
+                aggregate(reactions, my_name.as_deref())
+                    .into_iter()
+                    .filter(|count| count.mine.is_some())
+                    .map(|count| count.emoji)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let spans: Vec<Span> = EMOJIS
+            .iter()
+            .enumerate()
+            .flat_map(|(index, emoji)| {
+                let mut style = Style::default();
+                if mine.contains(emoji) {
+                    style = style.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                }
+                if index == picker.index {
+                    style = style.bg(Color::LightCyan);
+                }
+
+                [Span::styled(format!(" {} ", emoji), style), Span::raw(" ")]
+            })
+            .collect();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("react (Enter: toggle, Esc: close)");
+
+        let area: Rect = centered_rect(f.size(), 50, 3);
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    }
+}
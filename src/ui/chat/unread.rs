@@ -0,0 +1,154 @@
+use std::ops::Bound;
+use std::time::Duration;
+
+use crate::app_event::TaskResult;
+use crate::rpc::{
+    channel::ChannelId, message::MessageId, name::MessageName, ycchat::v1::models::Message,
+};
+
+use super::history::History;
+use super::mention::is_mention;
+use super::ChatUi;
+
+/// How long the user has to stay on a message before it is acknowledged.
+const ACKNOWLEDGE_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Default)]
+pub struct Unread {
+    pub count: usize,
+    pub mentions: usize,
+    /// oldest unread message, where the "new messages" divider goes
+    pub first: Option<MessageId>,
+}
+
+impl Unread {
+    pub fn add(&mut self, message_id: MessageId, is_mention: bool) {
+        self.count += 1;
+        if is_mention {
+            self.mentions += 1;
+        }
+        self.first = Some(self.first.map_or(message_id, |first| first.min(message_id)));
+    }
+}
+
+/// Messages from other users that are newer than `read_up_to`.
+fn count_unread(history: &History, read_up_to: MessageId, my_name: Option<&str>) -> Unread {
+    let mut unread = Unread::default();
+
+    for (&message_id, message) in history
+        .messages
+        .range((Bound::Excluded(read_up_to), Bound::Unbounded))
+        .filter(|(_, message)| Some(message.owner.as_str()) != my_name)
+    {
+        let is_mention = my_name.is_some_and(|my_name| is_mention(message, my_name));
+        unread.add(message_id, is_mention);
+    }
+
+    unread
+}
+
+impl<'a> ChatUi<'a> {
+    /// Counts a message from the stream as unread; `mark_read` clears it once it is seen.
+    pub(super) fn add_unread(&mut self, channel_id: ChannelId, message: &Message) {
+        let my_name = self.my_name();
+        if Some(&message.owner) == my_name.as_ref() {
+            return;
+        }
+
+        let message_id = match message.name.parse::<MessageName>() {
+            Ok(name) => name.message_id,
+            Err(_) => return,
+        };
+
+        let is_mention = my_name.is_some_and(|my_name| is_mention(message, &my_name));
+        self.unread
+            .entry(channel_id)
+            .or_default()
+            .add(message_id, is_mention);
+    }
+
+    pub(super) fn unread(&self, channel_id: ChannelId) -> Unread {
+        self.unread.get(&channel_id).copied().unwrap_or_default()
+    }
+
+    /// Treats everything up to the newest visible message of the shown channel as read,
+    /// and acknowledges it once the user stayed there for `ACKNOWLEDGE_DELAY`.
+    pub(super) fn mark_read(&mut self) {
+        if !self.is_visible {
+            return;
+        }
+
+        let channel_id = match self.channel_id() {
+            Some(channel_id) => channel_id,
+            None => return,
+        };
+        let history = match self.histories.get(&channel_id) {
+            Some(history) => history,
+            None => return,
+        };
+
+        // the selected message, or the newest one while following the bottom
+        let newest_visible = self
+            .message_index
+            .and_then(|index| history.message_at(index))
+            .and_then(|message| message.name.parse::<MessageName>().ok())
+            .map(|name| name.message_id)
+            .or_else(|| history.last_seen());
+        let newest_visible = match newest_visible {
+            Some(newest_visible) => newest_visible,
+            None => return,
+        };
+
+        // scrolling back up does not make messages unread again
+        let read_up_to = history.acknowledged.map_or(newest_visible, |acknowledged| {
+            acknowledged.max(newest_visible)
+        });
+        let unread = count_unread(history, read_up_to, self.my_name().as_deref());
+        let is_acknowledged =
+            read_up_to != newest_visible || history.acknowledged == Some(newest_visible);
+
+        if unread.count == 0 {
+            self.unread.remove(&channel_id);
+        } else {
+            self.unread.insert(channel_id, unread);
+        }
+
+        if is_acknowledged || self.pending_acknowledge == Some((channel_id, newest_visible)) {
+            return;
+        }
+        self.pending_acknowledge = Some((channel_id, newest_visible));
+
+        let events = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            app_state.events.clone()
+        };
+
+        events.spawn(async move {
+            tokio::time::sleep(ACKNOWLEDGE_DELAY).await;
+
+            TaskResult::AcknowledgeDue(channel_id, newest_visible)
+        });
+    }
+
+    /// Sends the acknowledgement unless the user moved on to another message meanwhile.
+    pub(super) fn acknowledge(&mut self, channel_id: ChannelId, message_id: MessageId) {
+        if self.pending_acknowledge != Some((channel_id, message_id)) {
+            return;
+        }
+        self.pending_acknowledge = None;
+
+        let (rpc, events) = self.rpc_and_events();
+
+        events.spawn(async move {
+            let result = rpc.message().acknowledge_message(message_id).await;
+
+            TaskResult::Acknowledged {
+                channel_id,
+                message_id,
+                result,
+            }
+        });
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cell::RefCell,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use ratatui::{
+    prelude::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Text,
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use tui_textarea::{Input, Key, TextArea};
+
+use crate::{
+    app_event::{AppEvent, TaskResult},
+    app_state::AppState,
+    rpc::error::ClientError,
+};
+
+use super::{Scene, Ui};
+
+/// Deleting an account takes two steps: an optional reason, then the username typed
+/// back as confirmation.
+pub struct DeleteAccountUi<'a> {
+    app_state: Arc<Mutex<RefCell<AppState>>>,
+    input_reason: TextArea<'a>,
+    input_username: TextArea<'a>,
+    error_message: Option<String>,
+    is_pending: bool,
+    step: Step,
+}
+
+enum Step {
+    Reason,
+    Confirm,
+}
+
+impl<'a> DeleteAccountUi<'a> {
+    pub fn new(app_state: Arc<Mutex<RefCell<AppState>>>) -> Self {
+        let mut me = Self {
+            app_state,
+            input_reason: TextArea::default(),
+            input_username: TextArea::default(),
+            error_message: None,
+            is_pending: false,
+            step: Step::Reason,
+        };
+        me.clear();
+
+        me
+    }
+
+    fn clear(&mut self) {
+        self.input_reason = TextArea::default();
+        self.input_reason.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("reason (optional)"),
+        );
+        self.input_reason
+            .set_placeholder_text("Why are you leaving? Alt+Enter for a new line.");
+
+        self.input_username = TextArea::default();
+        self.input_username
+            .set_block(Block::default().borders(Borders::ALL).title("username"));
+        self.input_username
+            .set_placeholder_text("Type your username to confirm");
+
+        self.error_message = None;
+        self.step(Step::Reason);
+    }
+
+    fn step(&mut self, step: Step) {
+        let enable_style = Style::default().bg(Color::White);
+        let disable_style = Style::default();
+
+        match step {
+            Step::Reason => {
+                self.input_reason.set_cursor_style(enable_style);
+                self.input_username.set_cursor_style(disable_style);
+            }
+            Step::Confirm => {
+                self.input_reason.set_cursor_style(disable_style);
+                self.input_username.set_cursor_style(enable_style);
+            }
+        }
+
+        self.step = step;
+    }
+
+    /// Checks the typed username and deletes the account in the background; the result
+    /// arrives as `TaskResult::AccountDeleted`.
+    fn submit(&mut self) -> Result<(), ClientError> {
+        let reason = self.input_reason.lines().join("\n").trim().to_string();
+        let typed_username = self.input_username.lines().join("").trim().to_string();
+
+        let (rpc, events) = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            let username = match &app_state.user {
+                Some(user_state) => &user_state.username,
+                None => return Err(ClientError::NotSignedIn),
+            };
+
+            if typed_username != *username {
+                return Err(ClientError::Validation(
+                    "The username does not match.".to_string(),
+                ));
+            }
+
+            (app_state.rpc.clone(), app_state.events.clone())
+        };
+
+        events.spawn(async move {
+            TaskResult::AccountDeleted(rpc.account().delete_account(reason).await)
+        });
+
+        self.is_pending = true;
+
+        Ok(())
+    }
+
+    fn sign_out(&mut self) {
+        let mut app_state = self.app_state.lock().unwrap();
+        app_state.get_mut().sign_out(Some(
+            "Your account has been deleted. Goodbye, and thanks for chatting!".to_string(),
+        ));
+    }
+}
+
+impl<'a> Ui for DeleteAccountUi<'a> {
+    fn ui(&self, f: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(2),
+                Constraint::Min(5),    // reason
+                Constraint::Length(3), // username
+                Constraint::Length(1),
+            ])
+            .split(f.size());
+
+        let title = Paragraph::new(Text::styled(
+            "Delete account",
+            Style::default().fg(Color::LightRed),
+        ))
+        .block(Block::default().borders(Borders::ALL));
+
+        let hint = match self.step {
+            Step::Reason => {
+                "This deletes your account for good. Press Enter to continue, Esc to go back."
+            }
+            Step::Confirm => {
+                "Type your username and press Enter to delete the account. Esc to go back."
+            }
+        };
+
+        let err_message = match &self.error_message {
+            Some(err_message) => err_message.clone(),
+            None if self.is_pending => "Deleting account...".to_string(),
+            None => String::new(),
+        };
+
+        f.render_widget(title, layout[0]);
+        f.render_widget(
+            Paragraph::new(hint)
+                .style(Style::default().fg(Color::DarkGray))
+                .wrap(Wrap { trim: true }),
+            layout[1],
+        );
+        f.render_widget(self.input_reason.widget(), layout[2]);
+        if let Step::Confirm = self.step {
+            f.render_widget(self.input_username.widget(), layout[3]);
+        }
+        f.render_widget(
+            Paragraph::new(Text::styled(
+                err_message,
+                Style::default().fg(Color::LightRed),
+            )),
+            layout[4],
+        );
+    }
+
+    fn event_handle<'me>(
+        &'me mut self,
+        event: std::io::Result<crossterm::event::Event>,
+    ) -> Pin<Box<dyn std::future::Future<Output = io::Result<Scene>> + Send + 'me>> {
+        let me: &'me mut Self = self;
+
+        Box::pin(async {
+            let input: Input = event?.into();
+
+            if me.is_pending {
+                return Ok(Scene::DeleteAccount);
+            }
+
+            match (&me.step, input) {
+                (Step::Reason, Input { key: Key::Esc, .. }) => {
+                    me.clear();
+                    return Ok(Scene::AfterSignIn);
+                }
+                (
+                    Step::Reason,
+                    Input {
+                        key: Key::Enter,
+                        alt: false,
+                        shift: false,
+                        ..
+                    },
+                ) => {
+                    me.error_message = None;
+                    me.step(Step::Confirm);
+                }
+                (
+                    Step::Reason,
+                    Input {
+                        key: Key::Enter, ..
+                    },
+                ) => me.input_reason.insert_newline(),
+                (Step::Reason, input) => {
+                    me.input_reason.input(input);
+                }
+                (Step::Confirm, Input { key: Key::Esc, .. }) => {
+                    me.error_message = None;
+                    me.step(Step::Reason);
+                }
+                (
+                    Step::Confirm,
+                    Input {
+                        key: Key::Enter, ..
+                    },
+                ) => match me.submit() {
+                    Ok(_) => me.error_message = None,
+                    Err(ClientError::NotSignedIn) => return Ok(Scene::SignIn),
+                    Err(err) => me.error_message = Some(err.to_string()),
+                },
+                (Step::Confirm, input) => {
+                    me.input_username.input(input);
+                }
+            };
+
+            Ok(Scene::DeleteAccount)
+        })
+    }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::AccountDeleted(result)) => result,
+            _ => return None,
+        };
+
+        self.is_pending = false;
+
+        match result {
+            Ok(()) => {
+                self.clear();
+                self.sign_out();
+
+                Some(Scene::Main)
+            }
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+
+                None
+            }
+        }
+    }
+}
@@ -3,10 +3,16 @@ use std::{io, pin::Pin};
 use crossterm::event::Event;
 use ratatui::Frame;
 
+use crate::app_event::AppEvent;
+
+pub mod account;
 pub mod after_sign_in;
+pub mod chat;
+pub mod delete_account;
 pub mod profile;
 pub mod sign_in;
 pub mod sign_up;
+pub mod validation;
 pub mod welcome;
 
 pub trait Ui {
@@ -16,13 +22,28 @@ pub trait Ui {
         &'me mut self,
         event: std::io::Result<Event>,
     ) -> Pin<Box<dyn std::future::Future<Output = io::Result<Scene>> + Send + 'me>>;
+
+    /// Called when the app switches to this scene.
+    fn on_enter(&mut self) {}
+
+    /// Called when the app switches away from this scene.
+    fn on_leave(&mut self) {}
+
+    /// Handles an event from a background task or the server; returns the scene to switch to.
+    fn app_event_handle(&mut self, _event: AppEvent) -> Option<Scene> {
+        None
+    }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Scene {
     Main,
     SignIn,
     SignUp,
     AfterSignIn,
     Profile,
+    Account,
+    DeleteAccount,
+    Chat,
     Quit, // close app
 }
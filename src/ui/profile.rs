@@ -1,6 +1,5 @@
 use std::{
     cell::RefCell,
-    error::Error,
     sync::{Arc, Mutex},
 };
 
@@ -14,8 +13,9 @@ use ratatui::{
 use tui_textarea::{Input, Key, TextArea};
 
 use crate::{
+    app_event::{AppEvent, TaskResult},
     app_state::AppState,
-    rpc::{me::MeUserService, user::UserService, ycchat::v1::models::User},
+    rpc::{error::ClientError, name::UserName, user::UserId, ycchat::v1::models::User},
 };
 
 use super::{Scene, Ui};
@@ -25,6 +25,8 @@ pub struct ProfileUi<'a> {
     app_state: Arc<Mutex<RefCell<AppState>>>,
     input_display_name: TextArea<'a>,
     input_description: TextArea<'a>,
+    error_message: Option<String>,
+    is_pending: bool,
 }
 
 enum Focus {
@@ -57,6 +59,8 @@ impl<'a> ProfileUi<'a> {
             app_state,
             input_display_name,
             input_description,
+            error_message: None,
+            is_pending: false,
         }
     }
 
@@ -93,21 +97,21 @@ impl<'a> ProfileUi<'a> {
         self.current_focus = focus;
     }
 
-    async fn submit(&mut self) -> Result<User, Box<dyn Error>> {
+    /// Saves the profile in the background; the result arrives as `TaskResult::Profile`.
+    fn submit(&mut self) -> Result<(), ClientError> {
         let display_name = self.input_display_name.lines().join("").trim().to_string();
         let description = self.input_description.lines().join("").trim().to_string();
 
-        let (sign_in_response, user) = {
+        let (rpc, user) = {
             let app_state = self.app_state.lock().unwrap();
             let app_state = &app_state.borrow();
 
             let user_state = if let Some(user_state) = &app_state.user {
                 user_state
             } else {
-                return Err("invalid user state".into());
+                return Err(ClientError::NotSignedIn);
             };
 
-            let sign_in_response = user_state.sign_in_response.clone();
             let user = if let Some(user) = &user_state.user {
                 let mut user = user.clone();
                 user.display_name = display_name;
@@ -115,8 +119,12 @@ impl<'a> ProfileUi<'a> {
 
                 user
             } else {
-                let user_id = user_state.sign_in_response.user_id.clone();
-                let name = format!("user/{}", user_id);
+                let user_id: UserId = user_state
+                    .sign_in_response
+                    .user_id
+                    .parse()
+                    .map_err(|_| ClientError::NotSignedIn)?;
+                let name = UserName::new(user_id).to_string();
 
                 let avatar = None;
                 let region_code = "1";
@@ -136,39 +144,30 @@ impl<'a> ProfileUi<'a> {
                 }
             };
 
-            (sign_in_response, user)
+            (app_state.rpc.clone(), user)
         };
 
-        let mut me_user_service = {
-            let auth_state = { Arc::new(tokio::sync::Mutex::new(sign_in_response.clone())) };
+        let events = self.app_state.lock().unwrap().borrow().events.clone();
 
-            MeUserService::new(auth_state).await?
-        };
-
-        let mut user_service = {
-            let auth_state = { Arc::new(tokio::sync::Mutex::new(sign_in_response.clone())) };
-
-            UserService::new(auth_state).await?
-        };
+        events.spawn(async move {
+            let result = async {
+                let mut user_service = rpc.user();
 
-        let is_user_exist = me_user_service.get_user().await.is_ok();
+                let is_user_exist = rpc.me().get_user().await.is_ok();
 
-        let res = if is_user_exist {
-            user_service.update_user(user).await?
-        } else {
-            user_service.create_user(user).await?
-        };
+                if is_user_exist {
+                    user_service.update_user(user).await
+                } else {
+                    user_service.create_user(user).await
+                }
+            };
 
-        {
-            let app_state = self.app_state.lock().unwrap();
-            let app_state = &mut app_state.borrow_mut();
+            TaskResult::Profile(result.await)
+        });
 
-            if let Some(user_state) = &mut app_state.user {
-                user_state.user = Some(res.clone());
-            }
-        }
+        self.is_pending = true;
 
-        Ok(res)
+        Ok(())
     }
 }
 
@@ -196,6 +195,20 @@ impl<'a> Ui for ProfileUi<'a> {
 
         f.render_widget(self.input_display_name.widget(), layout[1]);
         f.render_widget(self.input_description.widget(), layout[2]);
+
+        let err_message = match &self.error_message {
+            Some(err_message) => err_message.clone(),
+            None if self.is_pending => "Saving...".to_string(),
+            None => String::new(),
+        };
+
+        f.render_widget(
+            Paragraph::new(Text::styled(
+                err_message,
+                Style::default().fg(Color::LightRed),
+            )),
+            layout[3],
+        );
     }
 
     fn event_handle<'me>(
@@ -219,14 +232,14 @@ impl<'a> Ui for ProfileUi<'a> {
                 }
                 Input {
                     key: Key::Enter, ..
-                } => {
-                    let res = me.submit().await;
-                    if let Ok(res) = res {
-                        return Ok(Scene::AfterSignIn);
-                    } else {
-                        return Ok(Scene::SignIn);
-                    }
-                }
+                } if !me.is_pending => match me.submit() {
+                    Ok(_) => me.error_message = None,
+                    Err(ClientError::NotSignedIn) => return Ok(Scene::SignIn),
+                    Err(err) => me.error_message = Some(err.to_string()),
+                },
+                Input {
+                    key: Key::Enter, ..
+                } => {}
                 input => {
                     match me.current_focus {
                         Focus::DisplayName => me.input_display_name.input(input),
@@ -238,4 +251,31 @@ impl<'a> Ui for ProfileUi<'a> {
             Ok(Scene::Profile)
         })
     }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::Profile(result)) => result,
+            _ => return None,
+        };
+
+        self.is_pending = false;
+
+        match result {
+            Ok(user) => {
+                self.error_message = None;
+
+                let mut app_state = self.app_state.lock().unwrap();
+                if let Some(user_state) = &mut app_state.get_mut().user {
+                    user_state.user = Some(user);
+                }
+
+                Some(Scene::AfterSignIn)
+            }
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+
+                None
+            }
+        }
+    }
 }
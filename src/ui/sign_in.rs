@@ -1,6 +1,5 @@
 use std::{
     cell::RefCell,
-    error::Error,
     io,
     pin::Pin,
     sync::{Arc, Mutex},
@@ -16,8 +15,9 @@ use ratatui::{
 use tui_textarea::{Input, Key, TextArea};
 
 use crate::{
+    app_event::{AppEvent, TaskResult},
     app_state::{AppState, UserState},
-    rpc::{auth::AuthService, me::MeUserService, ycchat::v1::services::auth::SignInResponse},
+    rpc::{error::ClientError, interceptor::reauth::Credentials},
 };
 
 use super::{Scene, Ui};
@@ -27,6 +27,7 @@ pub struct SignInUi<'a> {
     input_username: TextArea<'a>,
     input_password: TextArea<'a>,
     error_message: Option<String>,
+    is_pending: bool,
     current_focus: Focus,
 }
 
@@ -51,10 +52,15 @@ impl<'a> SignInUi<'a> {
             input_username,
             input_password,
             error_message: None,
+            is_pending: false,
             current_focus: Focus::UserName,
         }
     }
 
+    pub fn set_error_message(&mut self, error_message: Option<String>) {
+        self.error_message = error_message;
+    }
+
     fn focus(&mut self, focus: Focus) {
         let enable_style = Style::default().bg(Color::White);
         let disable_style = Style::default();
@@ -73,32 +79,49 @@ impl<'a> SignInUi<'a> {
         self.current_focus = focus;
     }
 
-    async fn submit(&mut self) -> Result<SignInResponse, Box<dyn Error>> {
+    /// Validates the form and signs in in the background; the result arrives as
+    /// `TaskResult::SignIn`.
+    fn submit(&mut self) -> Result<(), ClientError> {
         let username = self.input_username.lines().join("").trim().to_string();
         let password = self.input_password.lines().join("").trim().to_string();
 
-        let mut auth_service = AuthService::new().await?;
-        let response = auth_service.sign_in(username.clone(), password).await?;
+        if username.is_empty() || password.is_empty() {
+            return Err(ClientError::Validation(
+                "Please enter your username and password.".to_string(),
+            ));
+        }
 
-        {
-            let mut me_user_service = {
-                let auth_state = { Arc::new(tokio::sync::Mutex::new(response.clone())) };
+        let (rpc, events) = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
 
-                MeUserService::new(auth_state).await?
-            };
+            (app_state.rpc.clone(), app_state.events.clone())
+        };
 
-            let user = if let Ok(user) = me_user_service.get_user().await {
-                Some(user)
-            } else {
-                None
+        events.spawn(async move {
+            let result = async {
+                let response = rpc
+                    .auth()
+                    .sign_in(username.clone(), password.clone())
+                    .await?;
+
+                let credentials = Credentials {
+                    username: username.clone(),
+                    password,
+                };
+                rpc.set_auth(response.clone(), Some(credentials)).await;
+
+                let user = rpc.me().get_user().await.ok();
+
+                Ok(UserState::new(username, user, response))
             };
 
-            let mut app_state = self.app_state.lock().unwrap();
-            let app_state = app_state.get_mut();
-            app_state.user = Some(UserState::new(username, user, response.clone()));
-        }
+            TaskResult::SignIn(result.await)
+        });
 
-        Ok(response)
+        self.is_pending = true;
+
+        Ok(())
     }
 }
 
@@ -124,6 +147,7 @@ impl<'a> Ui for SignInUi<'a> {
 
         let err_message = match &self.error_message {
             Some(err_message) => err_message.clone(),
+            None if self.is_pending => "Signing in...".to_string(),
             None => String::new(),
         };
 
@@ -158,13 +182,13 @@ impl<'a> Ui for SignInUi<'a> {
                 }
                 Input {
                     key: Key::Enter, ..
-                } => {
-                    let sign_in_res = me.submit().await.unwrap();
-
-                    me.error_message = Some(sign_in_res.user_id);
-
-                    return Ok(Scene::AfterSignIn);
-                }
+                } if !me.is_pending => match me.submit() {
+                    Ok(_) => me.error_message = None,
+                    Err(err) => me.error_message = Some(err.to_string()),
+                },
+                Input {
+                    key: Key::Enter, ..
+                } => {}
                 input => {
                     match me.current_focus {
                         Focus::UserName => me.input_username.input(input),
@@ -176,4 +200,40 @@ impl<'a> Ui for SignInUi<'a> {
             Ok(Scene::SignIn)
         })
     }
+
+    fn on_enter(&mut self) {
+        if let Some(notice) = self.app_state.lock().unwrap().get_mut().notice.take() {
+            self.error_message = Some(notice);
+        }
+    }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::SignIn(result)) => result,
+            _ => return None,
+        };
+
+        self.is_pending = false;
+
+        match result {
+            Ok(user_state) => {
+                self.error_message = None;
+
+                let mut app_state = self.app_state.lock().unwrap();
+                let app_state = app_state.get_mut();
+                app_state.user = Some(user_state);
+                app_state.save_session();
+                app_state
+                    .connection
+                    .start(app_state.rpc.clone(), app_state.events.clone());
+
+                Some(Scene::AfterSignIn)
+            }
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+
+                None
+            }
+        }
+    }
 }
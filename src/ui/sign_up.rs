@@ -1,6 +1,5 @@
 use std::{
     cell::RefCell,
-    error::Error,
     io,
     pin::Pin,
     sync::{Arc, Mutex},
@@ -16,11 +15,12 @@ use ratatui::{
 use tui_textarea::{Input, Key, TextArea};
 
 use crate::{
-    app_state::AppState,
-    rpc::{auth::AuthService, ycchat::v1::services::auth::SignUpResponse},
+    app_event::{AppEvent, TaskResult},
+    app_state::{AppState, UserState},
+    rpc::{error::ClientError, interceptor::reauth::Credentials},
 };
 
-use super::{Scene, Ui};
+use super::{validation, Scene, Ui};
 
 pub struct SignUpUi<'a> {
     app_state: Arc<Mutex<RefCell<AppState>>>,
@@ -30,6 +30,8 @@ pub struct SignUpUi<'a> {
     input_password: TextArea<'a>,
     input_password_repeat: TextArea<'a>,
 
+    error_message: Option<String>,
+    is_pending: bool,
     current_focus: Focus,
 }
 
@@ -70,6 +72,8 @@ impl<'a> SignUpUi<'a> {
             input_email,
             input_password,
             input_password_repeat,
+            error_message: None,
+            is_pending: false,
             current_focus: Focus::UserName,
         }
     }
@@ -96,15 +100,79 @@ impl<'a> SignUpUi<'a> {
         self.current_focus = focus;
     }
 
-    async fn submit(&self) -> Result<SignUpResponse, Box<dyn Error>> {
+    /// Validates the form, signs up and then signs in in the background; the result arrives
+    /// as `TaskResult::SignUp`.
+    fn submit(&mut self) -> Result<(), ClientError> {
         let email = self.input_email.lines().join("").trim().to_string();
         let username = self.input_username.lines().join("").trim().to_string();
         let password = self.input_password.lines().join("").trim().to_string();
+        let password_repeat = self
+            .input_password_repeat
+            .lines()
+            .join("")
+            .trim()
+            .to_string();
 
-        let mut auth_service = AuthService::new().await?;
-        let response = auth_service.sign_up(email, username, password).await?;
+        if let Err(err) = validation::username(&username) {
+            self.focus(Focus::UserName);
+            return Err(err);
+        }
+        if let Err(err) = validation::email(&email) {
+            self.focus(Focus::Email);
+            return Err(err);
+        }
+        if let Err(err) = validation::password(&password) {
+            self.focus(Focus::Password);
+            return Err(err);
+        }
+        if password != password_repeat {
+            self.focus(Focus::PasswordRepeat);
+            return Err(ClientError::Validation(
+                "The passwords do not match.".to_string(),
+            ));
+        }
+
+        let (rpc, events) = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            (app_state.rpc.clone(), app_state.events.clone())
+        };
+
+        events.spawn(async move {
+            let result = async {
+                rpc.auth()
+                    .sign_up(email, username.clone(), password.clone())
+                    .await?;
+
+                let response = match rpc.auth().sign_in(username.clone(), password.clone()).await {
+                    Ok(response) => response,
+                    Err(err) => {
+                        tracing::warn!(error = %err, "signed up, but the automatic sign-in failed");
+                        return Ok(None);
+                    }
+                };
+
+                let credentials = Credentials {
+                    username: username.clone(),
+                    password,
+                };
+                rpc.set_auth(response.clone(), Some(credentials)).await;
 
-        Ok(response)
+                Ok(Some(UserState::new(username, None, response)))
+            };
+
+            TaskResult::SignUp(result.await)
+        });
+
+        self.is_pending = true;
+
+        Ok(())
+    }
+
+    /// Empties the form once the account exists.
+    fn reset(&mut self) {
+        *self = Self::new(self.app_state.clone());
     }
 
     fn disable_all_cursor_style(&mut self) {
@@ -143,6 +211,20 @@ impl<'a> Ui for SignUpUi<'a> {
         f.render_widget(self.input_email.widget(), layout[2]);
         f.render_widget(self.input_password.widget(), layout[3]);
         f.render_widget(self.input_password_repeat.widget(), layout[4]);
+
+        let err_message = match &self.error_message {
+            Some(err_message) => err_message.clone(),
+            None if self.is_pending => "Signing up...".to_string(),
+            None => String::new(),
+        };
+
+        f.render_widget(
+            Paragraph::new(Text::styled(
+                err_message,
+                Style::default().fg(Color::LightRed),
+            )),
+            layout[5],
+        );
     }
 
     fn event_handle<'me>(
@@ -166,11 +248,13 @@ impl<'a> Ui for SignUpUi<'a> {
                 }
                 Input {
                     key: Key::Enter, ..
-                } => {
-                    let _ = me.submit().await;
-
-                    return Ok(Scene::Main);
-                }
+                } if !me.is_pending => match me.submit() {
+                    Ok(_) => me.error_message = None,
+                    Err(err) => me.error_message = Some(err.to_string()),
+                },
+                Input {
+                    key: Key::Enter, ..
+                } => {}
                 input => {
                     match me.current_focus {
                         Focus::UserName => me.input_username.input(input),
@@ -184,4 +268,42 @@ impl<'a> Ui for SignUpUi<'a> {
             Ok(Scene::SignUp)
         })
     }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::SignUp(result)) => result,
+            _ => return None,
+        };
+
+        self.is_pending = false;
+
+        match result {
+            Ok(Some(user_state)) => {
+                self.reset();
+
+                let mut app_state = self.app_state.lock().unwrap();
+                let app_state = app_state.get_mut();
+                app_state.user = Some(user_state);
+                app_state.save_session();
+                app_state
+                    .connection
+                    .start(app_state.rpc.clone(), app_state.events.clone());
+
+                // a new account has no profile yet
+                Some(Scene::Profile)
+            }
+            Ok(None) => {
+                self.reset();
+                self.app_state.lock().unwrap().get_mut().notice =
+                    Some("Account created. Please sign in.".to_string());
+
+                Some(Scene::SignIn)
+            }
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+
+                None
+            }
+        }
+    }
 }
@@ -0,0 +1,100 @@
+use crate::rpc::error::ClientError;
+
+pub const MIN_USERNAME_LEN: usize = 3;
+pub const MAX_USERNAME_LEN: usize = 32;
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+fn invalid(message: impl Into<String>) -> Result<(), ClientError> {
+    Err(ClientError::Validation(message.into()))
+}
+
+/// Letters, digits, `_`, `-` and `.`, starting with a letter or digit.
+pub fn username(username: &str) -> Result<(), ClientError> {
+    let len = username.chars().count();
+
+    if !(MIN_USERNAME_LEN..=MAX_USERNAME_LEN).contains(&len) {
+        return invalid(format!(
+            "The username must be {} to {} characters long.",
+            MIN_USERNAME_LEN, MAX_USERNAME_LEN
+        ));
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return invalid("The username may only contain letters, digits, '_', '-' and '.'.");
+    }
+    if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return invalid("The username must start with a letter or digit.");
+    }
+
+    Ok(())
+}
+
+/// A syntax check only; whether the address exists is up to the server.
+pub fn email(email: &str) -> Result<(), ClientError> {
+    let is_valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+
+    if !is_valid {
+        return invalid("Please enter a valid email address.");
+    }
+
+    Ok(())
+}
+
+/// At least `MIN_PASSWORD_LEN` characters, mixing letters with digits or symbols.
+pub fn password(password: &str) -> Result<(), ClientError> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return invalid(format!(
+            "The password must be at least {} characters long.",
+            MIN_PASSWORD_LEN
+        ));
+    }
+    if !password.chars().any(char::is_alphabetic) || password.chars().all(char::is_alphabetic) {
+        return invalid("The password must mix letters with digits or symbols.");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usernames() {
+        assert!(username("alice").is_ok());
+        assert!(username("bob_1.dev-x").is_ok());
+        assert!(username("al").is_err());
+        assert!(username("_alice").is_err());
+        assert!(username("al ice").is_err());
+        assert!(username(&"a".repeat(MAX_USERNAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn emails() {
+        assert!(email("alice@example.com").is_ok());
+        assert!(email("alice@localhost").is_err());
+        assert!(email("@example.com").is_err());
+        assert!(email("alice@example..com").is_err());
+        assert!(email("alice@@example.com").is_err());
+        assert!(email("al ice@example.com").is_err());
+    }
+
+    #[test]
+    fn passwords() {
+        assert!(password("hunter22!").is_ok());
+        assert!(password("short1").is_err());
+        assert!(password("onlyletters").is_err());
+        assert!(password("12345678").is_err());
+    }
+}
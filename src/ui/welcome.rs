@@ -1,4 +1,9 @@
-use std::{io, pin::Pin};
+use std::{
+    cell::RefCell,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 use crossterm::event::{self, Event, KeyCode};
 use ratatui::{
@@ -9,24 +14,84 @@ use ratatui::{
     Frame,
 };
 
+use crate::app_event::{AppEvent, TaskResult};
+use crate::app_state::{AppState, UserState};
+use crate::rpc::error::ClientError;
+
 use super::{Scene, Ui};
 
 pub struct WelcomeUi<'a> {
+    app_state: Arc<Mutex<RefCell<AppState>>>,
     selected_index: usize,
     list_items: Vec<ListItem<'a>>,
+    /// the saved session is being checked with the server
+    is_restoring: bool,
+    message: Option<String>,
+    /// left by the scene that signed the user out
+    notice: Option<String>,
 }
 
 impl<'a> WelcomeUi<'a> {
-    pub fn new() -> Self {
+    pub fn new(app_state: Arc<Mutex<RefCell<AppState>>>) -> Self {
         Self {
+            app_state,
             selected_index: 0,
             list_items: vec![
                 ListItem::new(Line::from(Span::styled("Sign In", Style::default()))),
                 ListItem::new(Line::from(Span::styled("Sign Up", Style::default()))),
                 ListItem::new(Line::from(Span::styled("Exit", Style::default()))),
             ],
+            is_restoring: false,
+            message: None,
+            notice: None,
         }
     }
+
+    /// Signs in with the saved session, if there is one, once the server accepts it.
+    pub fn restore_session(&mut self) {
+        let (rpc, events, loaded) = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            (
+                app_state.rpc.clone(),
+                app_state.events.clone(),
+                app_state.session.as_ref().map(|store| store.load()),
+            )
+        };
+
+        let session = match loaded {
+            Some(Ok(Some(session))) => session,
+            Some(Err(err)) => {
+                self.message = Some(format!("Could not restore the session: {}", err));
+                return;
+            }
+            Some(Ok(None)) | None => return,
+        };
+
+        events.spawn(async move {
+            let result = async {
+                rpc.set_auth(session.sign_in_response.clone(), None).await;
+
+                let user = rpc.me().get_user().await?;
+
+                Ok(UserState::new(
+                    session.username,
+                    Some(user),
+                    session.sign_in_response,
+                ))
+            };
+
+            let result = result.await;
+            if result.is_err() {
+                rpc.clear_auth().await;
+            }
+
+            TaskResult::RestoreSession(result)
+        });
+
+        self.is_restoring = true;
+    }
 }
 
 impl<'a> Ui for WelcomeUi<'a> {
@@ -36,7 +101,7 @@ impl<'a> Ui for WelcomeUi<'a> {
             .constraints([
                 Constraint::Min(3),
                 Constraint::Percentage(100),
-                // Constraint::Min(1),
+                Constraint::Min(1),
             ])
             .split(f.size());
 
@@ -59,6 +124,30 @@ impl<'a> Ui for WelcomeUi<'a> {
             .highlight_style(Style::default().bg(Color::LightCyan));
 
         f.render_stateful_widget(list, layout[1], &mut state);
+
+        let server = {
+            let app_state = self.app_state.lock().unwrap();
+            let app_state = app_state.borrow();
+
+            let config = &app_state.config;
+            let transport = if config.is_tls { "tls" } else { "plaintext" };
+
+            format!(
+                "server: {} [{}] ({})  profile: {}",
+                config.endpoint, transport, config.endpoint_source, config.profile
+            )
+        };
+
+        let status = match &self.message {
+            Some(message) => Span::styled(message.clone(), Style::default().fg(Color::LightRed)),
+            None if self.is_restoring => Span::raw("Restoring session..."),
+            None => match &self.notice {
+                Some(notice) => Span::styled(notice.clone(), Style::default().fg(Color::Green)),
+                None => Span::styled(server, Style::default().fg(Color::DarkGray)),
+            },
+        };
+
+        f.render_widget(Paragraph::new(Line::from(status)), layout[2]);
     }
 
     fn event_handle<'me>(
@@ -81,6 +170,9 @@ impl<'a> Ui for WelcomeUi<'a> {
                                 me.selected_index += 1;
                             }
                         }
+                        // signing in by hand would race the restore, which clears the
+                        // auth and the saved session when it fails; quitting still works
+                        KeyCode::Enter if me.is_restoring && me.selected_index != 2 => {}
                         KeyCode::Enter => match me.selected_index {
                             0 => return Ok(Scene::SignIn),
 
@@ -109,4 +201,44 @@ impl<'a> Ui for WelcomeUi<'a> {
             }
         })
     }
+
+    fn on_enter(&mut self) {
+        self.notice = self.app_state.lock().unwrap().get_mut().notice.take();
+    }
+
+    fn app_event_handle(&mut self, event: AppEvent) -> Option<Scene> {
+        let result = match event {
+            AppEvent::Task(TaskResult::RestoreSession(result)) => result,
+            _ => return None,
+        };
+
+        self.is_restoring = false;
+
+        let mut app_state = self.app_state.lock().unwrap();
+        let app_state = app_state.get_mut();
+
+        match result {
+            Ok(user_state) => {
+                self.message = None;
+
+                app_state.user = Some(user_state);
+                app_state
+                    .connection
+                    .start(app_state.rpc.clone(), app_state.events.clone());
+
+                Some(Scene::AfterSignIn)
+            }
+            Err(ClientError::Unauthenticated(_)) => {
+                app_state.clear_session();
+                self.message = Some("Saved session expired. Please sign in again.".to_string());
+
+                None
+            }
+            Err(err) => {
+                self.message = Some(format!("Could not restore the session: {}", err));
+
+                None
+            }
+        }
+    }
 }